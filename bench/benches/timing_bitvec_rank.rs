@@ -7,7 +7,9 @@ use criterion::{
     criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion, SamplingMode,
 };
 
-use jerky::bit_vector::{BitVector, BitVectorBuilder, NoIndex, Rank, Rank9SelIndex};
+use jerky::bit_vector::{
+    BitVector, BitVectorBuilder, NoIndex, Rank, Rank9SelIndex, SampledRankIndex,
+};
 
 const SAMPLE_SIZE: usize = 30;
 const WARM_UP_TIME: Duration = Duration::from_secs(5);
@@ -40,17 +42,37 @@ fn run_queries<R: Rank>(idx: &R, queries: &[usize]) {
 fn perform_bitvec_rank(group: &mut BenchmarkGroup<WallTime>, bits: &[bool], queries: &[usize]) {
     group.bench_function("jerky/BitVector", |b| {
         let mut builder = BitVectorBuilder::new();
-        builder.extend_bits(bits.iter().cloned());
+        builder.extend_bits(bits.iter().cloned()).unwrap();
         let idx: BitVector<NoIndex> = builder.freeze();
         b.iter(|| run_queries(&idx, &queries));
     });
 
     group.bench_function("jerky/BitVector<Rank9SelIndex>", |b| {
         let mut builder = BitVectorBuilder::new();
-        builder.extend_bits(bits.iter().cloned());
+        builder.extend_bits(bits.iter().cloned()).unwrap();
         let idx = builder.freeze::<Rank9SelIndex>();
         b.iter(|| run_queries(&idx, &queries));
     });
+
+    perform_sampled_rank::<1>(group, bits, queries);
+    perform_sampled_rank::<4>(group, bits, queries);
+    perform_sampled_rank::<16>(group, bits, queries);
+}
+
+fn perform_sampled_rank<const BLOCK: usize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    bits: &[bool],
+    queries: &[usize],
+) {
+    group.bench_function(
+        format!("jerky/BitVector<SampledRankIndex<{}>>", BLOCK),
+        |b| {
+            let mut builder = BitVectorBuilder::new();
+            builder.extend_bits(bits.iter().cloned()).unwrap();
+            let idx = builder.freeze::<SampledRankIndex<BLOCK>>();
+            b.iter(|| run_queries(&idx, queries));
+        },
+    );
 }
 
 macro_rules! criterion_common {
@@ -43,14 +43,14 @@ fn run_queries<S: Select>(idx: &S, queries: &[usize]) {
 fn perform_bitvec_select(group: &mut BenchmarkGroup<WallTime>, bits: &[bool], queries: &[usize]) {
     group.bench_function("jerky/BitVector", |b| {
         let mut builder = BitVectorBuilder::new();
-        builder.extend_bits(bits.iter().cloned());
+        builder.extend_bits(bits.iter().cloned()).unwrap();
         let idx: BitVector<NoIndex> = builder.freeze();
         b.iter(|| run_queries(&idx, &queries));
     });
 
     group.bench_function("jerky/BitVector<Rank9SelIndex>", |b| {
         let mut builder = BitVectorBuilder::new();
-        builder.extend_bits(bits.iter().cloned());
+        builder.extend_bits(bits.iter().cloned()).unwrap();
         let idx = builder.freeze::<Rank9SelIndex>();
         b.iter(|| run_queries(&idx, &queries));
     });
@@ -26,7 +26,9 @@ const PROTEINS_PSEF_STR: &str = include_str!("../data/texts/proteins.1MiB.txt");
 fn load_text(s: &str) -> CompactVector {
     let mut text = s.as_bytes().to_vec();
     let mut builder = BitVectorBuilder::new();
-    builder.extend_bits(core::iter::repeat(false).take(256));
+    builder
+        .extend_bits(core::iter::repeat(false).take(256))
+        .unwrap();
     for &c in &text {
         builder.set_bit(c as usize, true).unwrap();
     }
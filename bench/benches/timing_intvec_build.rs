@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion, SamplingMode,
+};
+
+use jerky::bit_vector::BitVectorBuilder;
+use jerky::int_vectors::{CompactVector, DacsByte};
+
+const SAMPLE_SIZE: usize = 30;
+const WARM_UP_TIME: Duration = Duration::from_secs(5);
+const MEASURE_TIME: Duration = Duration::from_secs(10);
+
+const DBLP_PSEF_STR: &str = include_str!("../data/lcps/dblp.1MiB.txt");
+const DNA_PSEF_STR: &str = include_str!("../data/lcps/dna.1MiB.txt");
+const PROTEINS_PSEF_STR: &str = include_str!("../data/lcps/proteins.1MiB.txt");
+
+fn parse_ints_from_str(s: &str) -> Vec<u32> {
+    let mut ints = vec![];
+    for l in s.split('\n') {
+        if !l.is_empty() {
+            ints.push(l.parse().unwrap());
+        }
+    }
+    ints
+}
+
+fn criterion_intvec_build_dblp(c: &mut Criterion) {
+    let mut group = c.benchmark_group("timing_intvec_build_dblp_1MiB");
+    group.sample_size(SAMPLE_SIZE);
+    group.warm_up_time(WARM_UP_TIME);
+    group.measurement_time(MEASURE_TIME);
+    group.sampling_mode(SamplingMode::Flat);
+
+    let vals = parse_ints_from_str(DBLP_PSEF_STR);
+    perform_intvec_build(&mut group, &vals);
+}
+
+fn criterion_intvec_build_dna(c: &mut Criterion) {
+    let mut group = c.benchmark_group("timing_intvec_build_dna_1MiB");
+    group.sample_size(SAMPLE_SIZE);
+    group.warm_up_time(WARM_UP_TIME);
+    group.measurement_time(MEASURE_TIME);
+    group.sampling_mode(SamplingMode::Flat);
+
+    let vals = parse_ints_from_str(DNA_PSEF_STR);
+    perform_intvec_build(&mut group, &vals);
+}
+
+fn criterion_intvec_build_proteins(c: &mut Criterion) {
+    let mut group = c.benchmark_group("timing_intvec_build_proteins_1MiB");
+    group.sample_size(SAMPLE_SIZE);
+    group.warm_up_time(WARM_UP_TIME);
+    group.measurement_time(MEASURE_TIME);
+    group.sampling_mode(SamplingMode::Flat);
+
+    let vals = parse_ints_from_str(PROTEINS_PSEF_STR);
+    perform_intvec_build(&mut group, &vals);
+}
+
+/// Splits `vals` into the byte levels and flag builders of [`DacsByte`],
+/// reserving each level to its exact size beforehand as `DacsByte::from_slice` does
+/// if `reserve` is set, or growing them on demand otherwise.
+fn split_levels(vals: &[u32], reserve: bool) -> (Vec<Vec<u8>>, Vec<BitVectorBuilder>) {
+    let mut level_lens = [0; 4];
+    for &x in vals {
+        level_lens[((32 - x.leading_zeros() as usize).max(1) - 1) / 8] += 1;
+    }
+    for j in (1..level_lens.len()).rev() {
+        level_lens[j - 1] += level_lens[j];
+    }
+    let num_levels = level_lens.iter().take_while(|&&len| len != 0).count();
+
+    let (mut level_data, mut flag_builders): (Vec<Vec<u8>>, Vec<BitVectorBuilder>) = if reserve {
+        (
+            level_lens[..num_levels]
+                .iter()
+                .map(|&len| Vec::with_capacity(len))
+                .collect(),
+            level_lens[..num_levels.saturating_sub(1)]
+                .iter()
+                .map(|&len| BitVectorBuilder::with_capacity(len))
+                .collect(),
+        )
+    } else {
+        (
+            vec![vec![]; num_levels],
+            vec![BitVectorBuilder::new(); num_levels.saturating_sub(1)],
+        )
+    };
+    for mut x in vals.iter().copied() {
+        for j in 0..num_levels {
+            level_data[j].push(x as u8);
+            x >>= 8;
+            if j == num_levels - 1 {
+                break;
+            }
+            flag_builders[j].push_bit(x != 0).unwrap();
+            if x == 0 {
+                break;
+            }
+        }
+    }
+    (level_data, flag_builders)
+}
+
+fn perform_intvec_build(group: &mut BenchmarkGroup<WallTime>, vals: &[u32]) {
+    group.bench_function("jerky/CompactVector", |b| {
+        b.iter(|| CompactVector::from_slice(vals).unwrap());
+    });
+
+    group.bench_function("jerky/DacsByte", |b| {
+        b.iter(|| -> DacsByte { DacsByte::from_slice(vals).unwrap() });
+    });
+
+    group.bench_function("jerky/DacsByte/levels_reserved", |b| {
+        b.iter(|| split_levels(vals, true));
+    });
+
+    group.bench_function("jerky/DacsByte/levels_unreserved", |b| {
+        b.iter(|| split_levels(vals, false));
+    });
+}
+
+criterion_group!(
+    benches,
+    criterion_intvec_build_dblp,
+    criterion_intvec_build_dna,
+    criterion_intvec_build_proteins
+);
+
+criterion_main!(benches);
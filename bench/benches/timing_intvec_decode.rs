@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
+
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion, SamplingMode,
+};
+
+use jerky::int_vectors::CompactVector;
+
+const SAMPLE_SIZE: usize = 30;
+const WARM_UP_TIME: Duration = Duration::from_secs(5);
+const MEASURE_TIME: Duration = Duration::from_secs(10);
+
+const SEED_VALS: u64 = 334;
+const NUM_VALS: usize = 1 << 20;
+
+fn gen_random_vals(len: usize, width: usize, seed: u64) -> Vec<usize> {
+    let mut rng = ChaChaRng::seed_from_u64(seed);
+    (0..len).map(|_| rng.gen_range(0..1 << width)).collect()
+}
+
+fn criterion_intvec_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("timing_intvec_decode");
+    group.sample_size(SAMPLE_SIZE);
+    group.warm_up_time(WARM_UP_TIME);
+    group.measurement_time(MEASURE_TIME);
+    group.sampling_mode(SamplingMode::Flat);
+
+    // Width 8 divides the word length and takes the unpacking fast path,
+    // while width 7 falls back to per-element extraction.
+    perform_intvec_decode(&mut group, 8);
+    perform_intvec_decode(&mut group, 7);
+}
+
+fn perform_intvec_decode(group: &mut BenchmarkGroup<WallTime>, width: usize) {
+    let vals = gen_random_vals(NUM_VALS, width, SEED_VALS);
+    let mut builder = CompactVector::new(width).unwrap();
+    builder.extend(vals.iter().cloned()).unwrap();
+    let idx = builder.freeze();
+
+    group.bench_function(format!("jerky/CompactVector/width={width}"), |b| {
+        let mut buf = Vec::with_capacity(idx.len());
+        b.iter(|| {
+            idx.decode_into(&mut buf);
+            if buf.len() != vals.len() {
+                panic!("Should not come.");
+            }
+        });
+    });
+}
+
+criterion_group!(benches, criterion_intvec_decode);
+
+criterion_main!(benches);
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion, SamplingMode,
+};
+
+use jerky::int_vectors::{Access, DacsByte};
+
+const SAMPLE_SIZE: usize = 30;
+const WARM_UP_TIME: Duration = Duration::from_secs(5);
+const MEASURE_TIME: Duration = Duration::from_secs(10);
+
+const DBLP_PSEF_STR: &str = include_str!("../data/lcps/dblp.1MiB.txt");
+const PROTEINS_PSEF_STR: &str = include_str!("../data/lcps/proteins.1MiB.txt");
+
+fn parse_ints_from_str(s: &str) -> Vec<u32> {
+    let mut ints = vec![];
+    for l in s.split('\n') {
+        if !l.is_empty() {
+            ints.push(l.parse().unwrap());
+        }
+    }
+    ints
+}
+
+fn criterion_intvec_iter_dblp(c: &mut Criterion) {
+    let mut group = c.benchmark_group("timing_intvec_iter_dblp_1MiB");
+    group.sample_size(SAMPLE_SIZE);
+    group.warm_up_time(WARM_UP_TIME);
+    group.measurement_time(MEASURE_TIME);
+    group.sampling_mode(SamplingMode::Flat);
+
+    let vals = parse_ints_from_str(DBLP_PSEF_STR);
+    perform_intvec_iter(&mut group, &vals);
+}
+
+fn criterion_intvec_iter_proteins(c: &mut Criterion) {
+    let mut group = c.benchmark_group("timing_intvec_iter_proteins_1MiB");
+    group.sample_size(SAMPLE_SIZE);
+    group.warm_up_time(WARM_UP_TIME);
+    group.measurement_time(MEASURE_TIME);
+    group.sampling_mode(SamplingMode::Flat);
+
+    let vals = parse_ints_from_str(PROTEINS_PSEF_STR);
+    perform_intvec_iter(&mut group, &vals);
+}
+
+fn check_sum(sum: usize) {
+    if sum == 0 {
+        panic!("Should not come.");
+    }
+}
+
+fn perform_intvec_iter(group: &mut BenchmarkGroup<WallTime>, vals: &[u32]) {
+    let idx: DacsByte = DacsByte::from_slice(vals).unwrap();
+
+    group.bench_function("jerky/DacsByte/iter", |b| {
+        b.iter(|| check_sum(idx.iter().sum()));
+    });
+
+    group.bench_function("jerky/DacsByte/access", |b| {
+        b.iter(|| check_sum((0..idx.len()).map(|i| idx.access(i).unwrap()).sum()));
+    });
+}
+
+criterion_group!(
+    benches,
+    criterion_intvec_iter_dblp,
+    criterion_intvec_iter_proteins
+);
+
+criterion_main!(benches);
@@ -22,7 +22,7 @@ fn show_memories(p: f64) {
 
     let bytes = {
         let mut b = BitVectorBuilder::new();
-        b.extend_bits(bits.iter().cloned());
+        b.extend_bits(bits.iter().cloned()).unwrap();
         let idx: BitVector<Rank9SelIndex> = b.freeze::<Rank9SelIndex>();
         let (len, data) = idx.data.to_bytes();
         let index = idx.index.to_bytes();
@@ -32,7 +32,7 @@ fn show_memories(p: f64) {
 
     let bytes = {
         let mut b = BitVectorBuilder::new();
-        b.extend_bits(bits.iter().cloned());
+        b.extend_bits(bits.iter().cloned()).unwrap();
         let idx: BitVector<Rank9SelIndex> = b.freeze::<Rank9SelIndex>();
         let (len, data) = idx.data.to_bytes();
         let index = idx.index.to_bytes();
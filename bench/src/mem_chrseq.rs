@@ -16,7 +16,9 @@ fn main() {
 fn load_text(s: &str) -> CompactVector {
     let mut text = s.as_bytes().to_vec();
     let mut builder = BitVectorBuilder::new();
-    builder.extend_bits(core::iter::repeat(false).take(256));
+    builder
+        .extend_bits(core::iter::repeat(false).take(256))
+        .unwrap();
     for &c in &text {
         builder.set_bit(c as usize, true).unwrap();
     }
@@ -0,0 +1,81 @@
+use std::time::Instant;
+
+use jerky::bit_vector::{
+    Access, BitVector, BitVectorBuilder, BitVectorIndex, NoIndex, NumBits, Rank, Rank9SelIndex,
+    Select,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
+
+const SEED_BITS: u64 = 334;
+const SEED_QUERIES: u64 = 114514;
+const NUM_BITS: usize = 1 << 20;
+const NUM_QUERIES: usize = 1000;
+
+// Usage: query_bench [num_bits] [num_queries]
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let num_bits = args.next().map_or(NUM_BITS, |a| {
+        a.parse().expect("num_bits must be an integer")
+    });
+    let num_queries = args.next().map_or(NUM_QUERIES, |a| {
+        a.parse().expect("num_queries must be an integer")
+    });
+
+    println!("structure,op,density,ns_per_op");
+    for p in [0.5, 0.1, 0.01] {
+        let bits = gen_random_bits(num_bits, p, SEED_BITS);
+        show_timings::<NoIndex>("BitVector<NoIndex>", &bits, p, num_queries);
+        show_timings::<Rank9SelIndex>("BitVector<Rank9SelIndex>", &bits, p, num_queries);
+    }
+}
+
+fn gen_random_bits(len: usize, p: f64, seed: u64) -> Vec<bool> {
+    let mut rng = ChaChaRng::seed_from_u64(seed);
+    (0..len).map(|_| rng.gen_bool(p)).collect()
+}
+
+fn gen_random_ints(len: usize, min: usize, max: usize, seed: u64) -> Vec<usize> {
+    let mut rng = ChaChaRng::seed_from_u64(seed);
+    (0..len).map(|_| rng.gen_range(min..=max)).collect()
+}
+
+fn show_timings<I: BitVectorIndex>(name: &str, bits: &[bool], p: f64, num_queries: usize) {
+    if bits.is_empty() || num_queries == 0 {
+        return;
+    }
+    let mut builder = BitVectorBuilder::new();
+    builder.extend_bits(bits.iter().cloned()).unwrap();
+    let bv: BitVector<I> = builder.freeze::<I>();
+
+    let positions = gen_random_ints(num_queries, 0, bits.len() - 1, SEED_QUERIES);
+    let ns = measure(num_queries, || {
+        positions.iter().filter(|&&q| bv.access(q).unwrap()).count()
+    });
+    print_row(name, "access", p, ns);
+
+    let ns = measure(num_queries, || {
+        positions.iter().map(|&q| bv.rank1(q).unwrap()).sum()
+    });
+    print_row(name, "rank1", p, ns);
+
+    if bv.num_ones() != 0 {
+        let ranks = gen_random_ints(num_queries, 0, bv.num_ones() - 1, SEED_QUERIES);
+        let ns = measure(num_queries, || {
+            ranks.iter().map(|&k| bv.select1(k).unwrap()).sum()
+        });
+        print_row(name, "select1", p, ns);
+    }
+}
+
+fn measure<F: FnMut() -> usize>(num_queries: usize, mut run_queries: F) -> f64 {
+    let start = Instant::now();
+    let sum = run_queries();
+    let elapsed = start.elapsed();
+    std::hint::black_box(sum);
+    elapsed.as_nanos() as f64 / num_queries as f64
+}
+
+fn print_row(name: &str, op: &str, p: f64, ns_per_op: f64) {
+    println!("{name},{op},{p},{ns_per_op:.3}");
+}
@@ -0,0 +1,19 @@
+use std::process::Command;
+
+#[test]
+fn query_bench_tiny_input() {
+    let output = Command::new(env!("CARGO_BIN_EXE_query_bench"))
+        .args(["100", "10"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some("structure,op,density,ns_per_op"));
+    let rows: Vec<&str> = lines.collect();
+    assert!(!rows.is_empty());
+    for row in rows {
+        assert_eq!(row.split(',').count(), 4);
+    }
+}
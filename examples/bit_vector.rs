@@ -2,7 +2,7 @@ use jerky::bit_vector::*;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut builder = BitVectorBuilder::new();
-    builder.extend_bits([true, false, true, false, true]);
+    builder.extend_bits([true, false, true, false, true])?;
     let bv = builder.freeze::<Rank9SelIndex>();
 
     assert_eq!(bv.num_bits(), 5);
@@ -0,0 +1,185 @@
+//! Select wrapper caching recent answers.
+
+use core::cell::Cell;
+
+use crate::bit_vector::{Access, BitVectorData, NumBits, Rank, Select, ToDense};
+
+type Slot = Cell<Option<(usize, Option<usize>)>>;
+
+/// Wrapper around a bit vector that caches recent select answers
+/// in a direct-mapped table.
+///
+/// A query `k` is stored in slot `k % cache_size`, replacing the previous entry,
+/// and is answered from the slot while no other query maps to it.
+/// Select1 and select0 queries have separate tables of `cache_size` slots each.
+///
+/// The cache only pays off for skewed query distributions in which
+/// the same `k` is asked repeatedly; for uniform queries over many ones,
+/// almost every query misses and the lookup is pure overhead.
+/// Since the table is updated through [`Cell`], the wrapper is not [`Sync`].
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::bit_vector::*;
+///
+/// let mut builder = BitVectorBuilder::new();
+/// builder.extend_bits([true, false, false, true])?;
+/// let bv = CachedSelect::new(builder.freeze::<Rank9SelIndex>(), 64);
+///
+/// assert_eq!(bv.select1(1), Some(3));
+/// assert_eq!(bv.select1(1), Some(3));
+/// assert_eq!(bv.select0(1), Some(2));
+/// assert_eq!(bv.rank1(4), Some(2));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachedSelect<B> {
+    inner: B,
+    slots1: Vec<Slot>,
+    slots0: Vec<Slot>,
+}
+
+impl<B> CachedSelect<B> {
+    /// Wraps `inner` with tables of `cache_size` slots for each of select1 and select0.
+    ///
+    /// If `cache_size` is zero, every query is forwarded to `inner`.
+    pub fn new(inner: B, cache_size: usize) -> Self {
+        Self {
+            inner,
+            slots1: vec![Cell::new(None); cache_size],
+            slots0: vec![Cell::new(None); cache_size],
+        }
+    }
+
+    /// Returns the number of slots for each of select1 and select0.
+    pub fn cache_size(&self) -> usize {
+        self.slots1.len()
+    }
+
+    /// Returns a reference to the wrapped bit vector.
+    pub const fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the wrapped bit vector.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+
+    /// Returns the answer for `k` from `slots`, computing and storing it on a miss.
+    #[inline(always)]
+    fn cached<F>(slots: &[Slot], k: usize, query: F) -> Option<usize>
+    where
+        F: FnOnce() -> Option<usize>,
+    {
+        if slots.is_empty() {
+            return query();
+        }
+        let slot = &slots[k % slots.len()];
+        match slot.get() {
+            Some((key, ans)) if key == k => ans,
+            _ => {
+                let ans = query();
+                slot.set(Some((k, ans)));
+                ans
+            }
+        }
+    }
+}
+
+impl<B: Select> Select for CachedSelect<B> {
+    fn select1(&self, k: usize) -> Option<usize> {
+        Self::cached(&self.slots1, k, || self.inner.select1(k))
+    }
+
+    fn select0(&self, k: usize) -> Option<usize> {
+        Self::cached(&self.slots0, k, || self.inner.select0(k))
+    }
+}
+
+impl<B: NumBits> NumBits for CachedSelect<B> {
+    fn num_bits(&self) -> usize {
+        self.inner.num_bits()
+    }
+
+    fn num_ones(&self) -> usize {
+        self.inner.num_ones()
+    }
+}
+
+impl<B: Access> Access for CachedSelect<B> {
+    fn access(&self, pos: usize) -> Option<bool> {
+        self.inner.access(pos)
+    }
+}
+
+impl<B: Rank> Rank for CachedSelect<B> {
+    fn rank1(&self, pos: usize) -> Option<usize> {
+        self.inner.rank1(pos)
+    }
+
+    fn rank0(&self, pos: usize) -> Option<usize> {
+        self.inner.rank0(pos)
+    }
+}
+
+impl<B: ToDense> ToDense for CachedSelect<B> {
+    fn to_dense(&self) -> BitVectorData {
+        self.inner.to_dense()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bit_vector::{BitVector, BitVectorIndex, Rank9SelIndex};
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    #[test]
+    fn test_matches_uncached() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        let data = BitVectorData::from_bits((0..1000).map(|_| rng.gen_bool(0.3)));
+        let bv = BitVector::<Rank9SelIndex>::new(data.clone(), BitVectorIndex::build(&data));
+        for cache_size in [0, 1, 7, 64] {
+            let cached = CachedSelect::new(bv.clone(), cache_size);
+            assert_eq!(cached.cache_size(), cache_size);
+            // Skewed queries hitting the same few keys interleaved with colliding ones.
+            for _ in 0..5000 {
+                let k = if rng.gen_bool(0.5) {
+                    rng.gen_range(0..4)
+                } else {
+                    rng.gen_range(0..=1000)
+                };
+                assert_eq!(cached.select1(k), bv.select1(k));
+                assert_eq!(cached.select0(k), bv.select0(k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_dense() {
+        let data = BitVectorData::from_bits([true, true, false, true, false, true]);
+        let bv = BitVector::<Rank9SelIndex>::new(data.clone(), BitVectorIndex::build(&data));
+        let cached = CachedSelect::new(bv, 2);
+        assert_eq!(cached.to_dense(), data);
+        assert_eq!(cached.to_dense().ones().count(), cached.num_ones());
+    }
+
+    #[test]
+    fn test_collisions() {
+        let data = BitVectorData::from_bits([true, true, false, true, false, true]);
+        let bv = BitVector::<Rank9SelIndex>::new(data.clone(), BitVectorIndex::build(&data));
+        let cached = CachedSelect::new(bv, 2);
+        // Keys 0, 2, 4 share a slot, and key 4 has no answer.
+        for k in [0, 2, 4, 0, 4, 2, 2, 0] {
+            assert_eq!(cached.select1(k), [Some(0), None, Some(3), None, None][k]);
+        }
+        assert_eq!(cached.select0(0), Some(2));
+        assert_eq!(cached.select0(2), None);
+        assert_eq!(cached.select0(0), Some(2));
+    }
+}
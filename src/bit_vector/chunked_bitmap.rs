@@ -0,0 +1,364 @@
+//! Bitmap switching between bit arrays and position lists per chunk.
+#![cfg(target_pointer_width = "64")]
+
+use anyhow::Result;
+
+use crate::bit_vector::{
+    Access, BitVectorBuilder, BitVectorData, BitVectorIndex, NoIndex, NumBits, Rank, Select,
+    ToDense, WORD_LEN,
+};
+use crate::int_vectors::CompactVector;
+
+/// Number of bits in a chunk.
+pub const CHUNK_BITS: usize = 1 << 16;
+
+/// Number of bits storing a position relative to the chunk start.
+const POSITION_WIDTH: usize = 16;
+
+/// Largest number of ones for which a position list is smaller than a bit array.
+const MAX_LIST_ONES: usize = CHUNK_BITS / POSITION_WIDTH;
+
+/// Container of the bits in one chunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Chunk {
+    /// Plain bits of a dense chunk.
+    Bits(BitVectorData),
+    /// Sorted positions of the ones in a sparse chunk, relative to the chunk start.
+    Positions(CompactVector),
+}
+
+/// Compressed bitmap for inputs that are dense in some regions and sparse in others,
+/// in the manner of Roaring bitmaps.
+///
+/// The universe is divided into chunks of [`CHUNK_BITS`] bits.
+/// A chunk with fewer than `CHUNK_BITS / 16` ones is stored as a sorted list
+/// of 16-bit positions in a [`CompactVector`], and any other chunk as plain bits
+/// in a [`BitVectorData`], so each chunk takes at most `CHUNK_BITS` bits.
+/// The number of ones before each chunk is kept to locate chunks.
+///
+/// Rank and select queries take time linear in the size of a chunk for bit arrays
+/// and logarithmic for position lists.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::bit_vector::*;
+///
+/// let bm = ChunkedBitmap::from_bits([true, false, false, true])?;
+///
+/// assert_eq!(bm.num_bits(), 4);
+/// assert_eq!(bm.access(3), Some(true));
+/// assert_eq!(bm.rank1(4), Some(2));
+/// assert_eq!(bm.select1(1), Some(3));
+/// assert_eq!(bm.select0(1), Some(2));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkedBitmap {
+    len: usize,
+    chunks: Vec<Chunk>,
+    /// `ranks[c]` is the number of ones before chunk `c`, followed by the total.
+    ranks: Vec<usize>,
+}
+
+impl ChunkedBitmap {
+    /// Creates a new bitmap from a bit iterator.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if a position list cannot be built.
+    pub fn from_bits<I: IntoIterator<Item = bool>>(bits: I) -> Result<Self> {
+        Self::from_data(&BitVectorData::from_bits(bits))
+    }
+
+    /// Creates a new bitmap holding the bits of `data`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if a position list cannot be built.
+    pub fn from_data(data: &BitVectorData) -> Result<Self> {
+        let len = data.len();
+        let words = data.words();
+        let mut chunks = Vec::with_capacity(crate::utils::ceiled_divide(len, CHUNK_BITS));
+        let mut ranks = vec![0];
+        for lo in (0..len).step_by(CHUNK_BITS) {
+            let hi = (lo + CHUNK_BITS).min(len);
+            let wlo = lo / WORD_LEN;
+            let whi = crate::utils::ceiled_divide(hi, WORD_LEN);
+            let bits = BitVectorData::from_words(words[wlo..whi].to_vec(), hi - lo)?;
+            let num_ones = NoIndex.num_ones(&bits);
+            let chunk = if num_ones < MAX_LIST_ONES {
+                let positions: Vec<usize> = bits.ones().collect();
+                Chunk::Positions(CompactVector::from_slice_with_width(
+                    &positions,
+                    POSITION_WIDTH,
+                )?)
+            } else {
+                Chunk::Bits(bits)
+            };
+            chunks.push(chunk);
+            ranks.push(ranks[ranks.len() - 1] + num_ones);
+        }
+        Ok(Self { len, chunks, ranks })
+    }
+
+    /// Gets the number of chunks.
+    pub fn num_chunks(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Gets the number of chunks stored as position lists.
+    pub fn num_list_chunks(&self) -> usize {
+        self.chunks
+            .iter()
+            .filter(|c| matches!(c, Chunk::Positions(_)))
+            .count()
+    }
+
+    /// Gets the number of bytes occupied by the chunks and the chunk ranks.
+    pub fn size_in_bytes(&self) -> usize {
+        let chunks: usize = self
+            .chunks
+            .iter()
+            .map(|c| match c {
+                Chunk::Bits(data) => data.num_words() * WORD_LEN / 8,
+                Chunk::Positions(cv) => {
+                    crate::utils::ceiled_divide(cv.len() * cv.width(), WORD_LEN) * WORD_LEN / 8
+                }
+            })
+            .sum();
+        chunks + self.ranks.len() * core::mem::size_of::<usize>()
+    }
+
+    /// Gets the number of bits in chunk `c`.
+    #[inline(always)]
+    fn chunk_len(&self, c: usize) -> usize {
+        (self.len - c * CHUNK_BITS).min(CHUNK_BITS)
+    }
+
+    /// Gets the number of zeros before chunk `c`.
+    #[inline(always)]
+    fn chunk_rank0(&self, c: usize) -> usize {
+        (c * CHUNK_BITS).min(self.len) - self.ranks[c]
+    }
+
+    /// Gets the number of positions in `cv` less than `off`.
+    #[inline(always)]
+    fn list_rank1(cv: &CompactVector, off: usize) -> usize {
+        partition_point(cv.len(), |j| cv.get_int(j).unwrap() < off)
+    }
+}
+
+/// Returns the first index in `0..len` for which `pred` is false,
+/// assuming `pred` is true on a prefix.
+#[inline(always)]
+fn partition_point<F: Fn(usize) -> bool>(len: usize, pred: F) -> usize {
+    let (mut lo, mut hi) = (0, len);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+impl NumBits for ChunkedBitmap {
+    fn num_bits(&self) -> usize {
+        self.len
+    }
+
+    fn num_ones(&self) -> usize {
+        self.ranks[self.ranks.len() - 1]
+    }
+}
+
+impl Access for ChunkedBitmap {
+    fn access(&self, pos: usize) -> Option<bool> {
+        if self.len <= pos {
+            return None;
+        }
+        let (c, off) = (pos / CHUNK_BITS, pos % CHUNK_BITS);
+        match &self.chunks[c] {
+            Chunk::Bits(data) => data.access(off),
+            Chunk::Positions(cv) => {
+                let j = Self::list_rank1(cv, off);
+                Some(cv.get_int(j) == Some(off))
+            }
+        }
+    }
+}
+
+impl Rank for ChunkedBitmap {
+    fn rank1(&self, pos: usize) -> Option<usize> {
+        if self.len < pos {
+            return None;
+        }
+        let (c, off) = (pos / CHUNK_BITS, pos % CHUNK_BITS);
+        if c == self.chunks.len() {
+            return Some(self.num_ones());
+        }
+        let r = match &self.chunks[c] {
+            Chunk::Bits(data) => NoIndex.rank1(data, off)?,
+            Chunk::Positions(cv) => Self::list_rank1(cv, off),
+        };
+        Some(self.ranks[c] + r)
+    }
+
+    fn rank0(&self, pos: usize) -> Option<usize> {
+        Some(pos - self.rank1(pos)?)
+    }
+}
+
+impl Select for ChunkedBitmap {
+    fn select1(&self, k: usize) -> Option<usize> {
+        if self.num_ones() <= k {
+            return None;
+        }
+        let c = self.ranks.partition_point(|&r| r <= k) - 1;
+        let k = k - self.ranks[c];
+        let off = match &self.chunks[c] {
+            Chunk::Bits(data) => NoIndex.select1(data, k)?,
+            Chunk::Positions(cv) => cv.get_int(k)?,
+        };
+        Some(c * CHUNK_BITS + off)
+    }
+
+    fn select0(&self, k: usize) -> Option<usize> {
+        if self.len - self.num_ones() <= k {
+            return None;
+        }
+        let c = partition_point(self.chunks.len(), |c| self.chunk_rank0(c + 1) <= k);
+        let k = k - self.chunk_rank0(c);
+        let off = match &self.chunks[c] {
+            Chunk::Bits(data) => NoIndex.select0(data, k)?,
+            Chunk::Positions(cv) => {
+                // The j-th one is preceded by `cv[j] - j` zeros in the chunk.
+                let j = partition_point(cv.len(), |j| cv.get_int(j).unwrap() - j <= k);
+                k + j
+            }
+        };
+        debug_assert!(off < self.chunk_len(c));
+        Some(c * CHUNK_BITS + off)
+    }
+}
+
+impl ToDense for ChunkedBitmap {
+    fn to_dense(&self) -> BitVectorData {
+        let mut builder = BitVectorBuilder::with_capacity(self.len);
+        for (c, chunk) in self.chunks.iter().enumerate() {
+            match chunk {
+                Chunk::Bits(data) => builder.extend_from_words(data.words(), data.len()).unwrap(),
+                Chunk::Positions(cv) => {
+                    let start = builder.len();
+                    builder
+                        .extend_bits(core::iter::repeat(false).take(self.chunk_len(c)))
+                        .unwrap();
+                    for off in cv.iter() {
+                        builder.set_bit(start + off, true).unwrap();
+                    }
+                }
+            }
+        }
+        builder.into_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bit_vector::BitVector;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    fn gen_mixed(seed: u64) -> Vec<bool> {
+        let mut rng = ChaChaRng::seed_from_u64(seed);
+        let densities = [0.0, 0.001, 0.5, 0.05, 1.0, 0.9, 0.01];
+        let mut bits = vec![];
+        for &p in &densities {
+            bits.extend((0..CHUNK_BITS).map(|_| rng.gen_bool(p)));
+        }
+        bits.extend((0..1000).map(|_| rng.gen_bool(0.3)));
+        bits
+    }
+
+    #[test]
+    fn test_matches_bit_vector() {
+        let mut rng = ChaChaRng::seed_from_u64(114514);
+        let bits = gen_mixed(334);
+        let bm = ChunkedBitmap::from_bits(bits.iter().copied()).unwrap();
+        let bv = BitVector::from(BitVectorData::from_bits(bits.iter().copied()));
+        assert_eq!(bm.num_chunks(), 8);
+        assert_eq!(bm.num_list_chunks(), 5);
+        assert_eq!(bm.num_bits(), bv.num_bits());
+        assert_eq!(bm.num_ones(), bv.num_ones());
+
+        let mut positions: Vec<usize> = (0..2000).map(|_| rng.gen_range(0..=bits.len())).collect();
+        positions.extend((0..8).map(|c| c * CHUNK_BITS));
+        positions.extend([bits.len() - 1, bits.len(), bits.len() + 1]);
+        for pos in positions {
+            assert_eq!(bm.access(pos), bv.access(pos), "pos={pos}");
+            assert_eq!(bm.rank1(pos), bv.rank1(pos), "pos={pos}");
+            assert_eq!(bm.rank0(pos), bv.rank0(pos), "pos={pos}");
+        }
+        let num_zeros = bv.num_zeros();
+        let mut ks: Vec<usize> = (0..2000).map(|_| rng.gen_range(0..bits.len())).collect();
+        ks.extend([
+            0,
+            bv.num_ones() - 1,
+            bv.num_ones(),
+            num_zeros - 1,
+            num_zeros,
+        ]);
+        for k in ks {
+            assert_eq!(bm.select1(k), bv.select1(k), "k={k}");
+            assert_eq!(bm.select0(k), bv.select0(k), "k={k}");
+        }
+    }
+
+    #[test]
+    fn test_small() {
+        for len in [0, 1, 63, 64, 65, 1000] {
+            let bits: Vec<bool> = (0..len).map(|i| i % 7 == 0).collect();
+            let bm = ChunkedBitmap::from_bits(bits.iter().copied()).unwrap();
+            let bv = BitVector::from(BitVectorData::from_bits(bits.iter().copied()));
+            for pos in 0..=len {
+                assert_eq!(bm.access(pos), bv.access(pos));
+                assert_eq!(bm.rank1(pos), bv.rank1(pos));
+            }
+            for k in 0..=len {
+                assert_eq!(bm.select1(k), bv.select1(k));
+                assert_eq!(bm.select0(k), bv.select0(k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_dense() {
+        let bits = gen_mixed(334);
+        let bm = ChunkedBitmap::from_bits(bits.iter().copied()).unwrap();
+        let dense = bm.to_dense();
+        assert_eq!(dense, BitVectorData::from_bits(bits.iter().copied()));
+        assert_eq!(dense.ones().count(), bm.num_ones());
+        for len in [0, 1, 65] {
+            let bits: Vec<bool> = (0..len).map(|i| i % 7 == 0).collect();
+            let bm = ChunkedBitmap::from_bits(bits.iter().copied()).unwrap();
+            assert_eq!(bm.to_dense(), BitVectorData::from_bits(bits));
+        }
+    }
+
+    #[test]
+    fn test_size_in_bytes() {
+        let bits = gen_mixed(334);
+        let plain = crate::utils::ceiled_divide(bits.len(), WORD_LEN) * WORD_LEN / 8;
+        let bm = ChunkedBitmap::from_bits(bits).unwrap();
+        assert!(bm.size_in_bytes() < plain * 3 / 4);
+
+        let sparse = ChunkedBitmap::from_bits((0..CHUNK_BITS * 4).map(|i| i % 1000 == 0)).unwrap();
+        assert!(sparse.size_in_bytes() * 20 < CHUNK_BITS * 4 / 8);
+    }
+}
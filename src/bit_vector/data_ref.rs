@@ -0,0 +1,247 @@
+//! Borrowed bit vector data over externally owned words.
+
+use anyhow::{anyhow, Result};
+
+use crate::bit_vector::{Access, BitVectorData, NumBits, Rank, Select, ToDense, WORD_LEN};
+use crate::broadword;
+
+/// Borrowed counterpart of [`BitVectorData`](crate::bit_vector::BitVectorData)
+/// over a word slice owned elsewhere, such as a region of a larger memory map.
+///
+/// No index is built, so rank and select queries are answered by linear scans.
+/// [`NoIndex`](crate::bit_vector::NoIndex) answers its queries through this view.
+/// Bits beyond `len` in the last word are ignored, so the slice does not need
+/// to be zero-padded.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::bit_vector::*;
+///
+/// let words = [0b1001, usize::MAX];
+/// let data = BitVectorDataRef::new(&words, 4)?;
+///
+/// assert_eq!(data.num_ones(), 2);
+/// assert_eq!(data.access(3), Some(true));
+/// assert_eq!(data.rank1(4), Some(2));
+/// assert_eq!(data.select1(1), Some(3));
+/// assert_eq!(data.select0(1), Some(2));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitVectorDataRef<'a> {
+    words: &'a [usize],
+    len: usize,
+}
+
+impl<'a> BitVectorDataRef<'a> {
+    /// Creates a view of the first `len` bits of `words`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `words` holds fewer than `len` bits.
+    pub fn new(words: &'a [usize], len: usize) -> Result<Self> {
+        if words.len() < crate::utils::ceiled_divide(len, WORD_LEN) {
+            return Err(anyhow!(
+                "words must hold at least len={len} bits, but got {} bits.",
+                words.len().saturating_mul(WORD_LEN)
+            ));
+        }
+        Ok(Self::from_parts(words, len))
+    }
+
+    /// Creates a view of the first `len` bits of `words` without checking
+    /// that `words` holds them, for callers that uphold it already.
+    ///
+    /// Only the words covering `len` bits are kept.
+    pub(crate) fn from_parts(words: &'a [usize], len: usize) -> Self {
+        let num_words = crate::utils::ceiled_divide(len, WORD_LEN).min(words.len());
+        Self {
+            words: &words[..num_words],
+            len,
+        }
+    }
+
+    /// Returns the number of bits stored.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the data is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the words covering the `len` bits, including any padding bits.
+    pub const fn words(&self) -> &'a [usize] {
+        self.words
+    }
+
+    /// Returns the `wpos`-th word with the bits beyond `len` cleared.
+    #[inline(always)]
+    fn masked_word(&self, wpos: usize) -> usize {
+        let rest = self.len - wpos * WORD_LEN;
+        if rest < WORD_LEN {
+            self.words[wpos] & ((1 << rest) - 1)
+        } else {
+            self.words[wpos]
+        }
+    }
+}
+
+impl NumBits for BitVectorDataRef<'_> {
+    fn num_bits(&self) -> usize {
+        self.len
+    }
+
+    fn num_ones(&self) -> usize {
+        (0..self.words.len())
+            .map(|wpos| broadword::popcount(self.masked_word(wpos)))
+            .sum()
+    }
+}
+
+impl Access for BitVectorDataRef<'_> {
+    fn access(&self, pos: usize) -> Option<bool> {
+        if pos < self.len {
+            Some((self.words[pos / WORD_LEN] >> (pos % WORD_LEN)) & 1 == 1)
+        } else {
+            None
+        }
+    }
+}
+
+impl Rank for BitVectorDataRef<'_> {
+    fn rank1(&self, pos: usize) -> Option<usize> {
+        if self.len < pos {
+            return None;
+        }
+        let mut r = 0;
+        let (wpos, left) = (pos / WORD_LEN, pos % WORD_LEN);
+        for &w in &self.words[..wpos] {
+            r += broadword::popcount(w);
+        }
+        if left != 0 {
+            r += broadword::popcount(self.words[wpos] << (WORD_LEN - left));
+        }
+        Some(r)
+    }
+
+    fn rank0(&self, pos: usize) -> Option<usize> {
+        Some(pos - self.rank1(pos)?)
+    }
+}
+
+impl Select for BitVectorDataRef<'_> {
+    fn select1(&self, mut k: usize) -> Option<usize> {
+        for wpos in 0..self.words.len() {
+            match broadword::select_in_word_or_count(self.masked_word(wpos), k) {
+                Ok(sel) => return Some(wpos * WORD_LEN + sel),
+                Err(cnt) => k -= cnt,
+            }
+        }
+        None
+    }
+
+    fn select0(&self, mut k: usize) -> Option<usize> {
+        for wpos in 0..self.words.len() {
+            // Padding bits beyond `len` are cleared after the negation
+            // so that they are not counted as zeros.
+            let rest = self.len - wpos * WORD_LEN;
+            let w = !self.words[wpos];
+            let w = if rest < WORD_LEN {
+                w & ((1 << rest) - 1)
+            } else {
+                w
+            };
+            match broadword::select_in_word_or_count(w, k) {
+                Ok(sel) => return Some(wpos * WORD_LEN + sel),
+                Err(cnt) => k -= cnt,
+            }
+        }
+        None
+    }
+}
+
+impl ToDense for BitVectorDataRef<'_> {
+    fn to_dense(&self) -> BitVectorData {
+        BitVectorData::from_words(self.words.to_vec(), self.len).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bit_vector::{BitVector, NoIndex};
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    #[test]
+    fn test_matches_owned() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for len in [0, 1, 63, 64, 65, 300] {
+            for p in [0.0, 0.1, 0.5, 1.0] {
+                let data = BitVectorData::from_bits((0..len).map(|_| rng.gen_bool(p)));
+                let view = BitVectorDataRef::new(data.words(), len).unwrap();
+                let bv = BitVector::new(data.clone(), NoIndex);
+                assert_eq!(view.num_ones(), bv.num_ones());
+                for pos in 0..=len + 1 {
+                    assert_eq!(view.access(pos), bv.access(pos));
+                    assert_eq!(view.rank1(pos), bv.rank1(pos));
+                    assert_eq!(view.rank0(pos), bv.rank0(pos));
+                }
+                for k in 0..=len {
+                    assert_eq!(view.select1(k), bv.select1(k));
+                    assert_eq!(view.select0(k), bv.select0(k));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_new_huge_len() {
+        let e = BitVectorDataRef::new(&[0, 0], usize::MAX);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some(format!(
+                "words must hold at least len={} bits, but got 128 bits.",
+                usize::MAX
+            ))
+        );
+    }
+
+    #[test]
+    fn test_dirty_padding() {
+        let words = [usize::MAX, usize::MAX];
+        let view = BitVectorDataRef::new(&words, 70).unwrap();
+        assert_eq!(view.words().len(), 2);
+        assert_eq!(view.num_ones(), 70);
+        assert_eq!(view.select1(69), Some(69));
+        assert_eq!(view.select1(70), None);
+        assert_eq!(view.select0(0), None);
+        assert_eq!(view.rank1(70), Some(70));
+    }
+
+    #[test]
+    fn test_to_dense() {
+        let words = [0b1001, usize::MAX];
+        let view = BitVectorDataRef::new(&words, 70).unwrap();
+        let dense = view.to_dense();
+        assert_eq!(dense.len(), 70);
+        assert_eq!(dense.ones().count(), view.num_ones());
+        assert!((0..70).all(|i| dense.access(i) == view.access(i)));
+        assert_eq!(dense.words(), &[0b1001, 0b111111]);
+    }
+
+    #[test]
+    fn test_too_short() {
+        let words = [0usize];
+        let e = BitVectorDataRef::new(&words, 65);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("words must hold at least len=65 bits, but got 64 bits.".to_string())
+        );
+    }
+}
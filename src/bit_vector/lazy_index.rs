@@ -0,0 +1,187 @@
+//! Index wrapper deferring construction to the first query.
+
+use once_cell::sync::OnceCell;
+
+use crate::bit_vector::{BitVectorData, BitVectorIndex, NoIndex};
+
+/// Index that builds the wrapped index `I` on the first rank or select query
+/// and caches it for later queries.
+///
+/// This saves the construction time of bit vectors that are loaded
+/// but never queried with rank or select. Access never triggers the build,
+/// and neither does counting the ones, which falls back to a popcount of
+/// the data until the index exists.
+///
+/// The index is held in a [`once_cell::sync::OnceCell`], so `LazyIndex<I>` is
+/// [`Sync`] whenever `I` is. If several threads query an unbuilt index at once,
+/// one of them builds it while the others wait, and all share the result.
+/// The cell stands in for `std::sync::OnceLock`, which requires Rust 1.70
+/// while this crate supports Rust 1.61. This type requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use jerky::bit_vector::*;
+///
+/// let bv = BitVectorBuilder::from_bit(true, 100).freeze::<LazyIndex<Rank9SelIndex>>();
+/// assert_eq!(bv.access(3), Some(true));
+/// assert_eq!(bv.num_ones(), 100);
+/// assert!(!bv.index.is_built());
+///
+/// assert_eq!(bv.rank1(50), Some(50));
+/// assert!(bv.index.is_built());
+/// ```
+#[derive(Debug, Clone)]
+pub struct LazyIndex<I> {
+    index: OnceCell<I>,
+}
+
+impl<I> Default for LazyIndex<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I> LazyIndex<I> {
+    /// Creates an index that is built on its first query.
+    pub const fn new() -> Self {
+        Self {
+            index: OnceCell::new(),
+        }
+    }
+
+    /// Checks if the wrapped index has been built.
+    pub fn is_built(&self) -> bool {
+        self.index.get().is_some()
+    }
+
+    /// Returns the wrapped index, or [`None`] if it has not been built.
+    pub fn get(&self) -> Option<&I> {
+        self.index.get()
+    }
+}
+
+impl<I: BitVectorIndex> LazyIndex<I> {
+    /// Returns the wrapped index, building it from `data` if needed.
+    pub fn get_or_build(&self, data: &BitVectorData) -> &I {
+        self.index.get_or_init(|| I::build(data))
+    }
+}
+
+impl<I: BitVectorIndex> BitVectorIndex for LazyIndex<I> {
+    fn build(_: &BitVectorData) -> Self {
+        Self::new()
+    }
+
+    fn num_ones(&self, data: &BitVectorData) -> usize {
+        match self.index.get() {
+            Some(index) => index.num_ones(data),
+            None => NoIndex.num_ones(data),
+        }
+    }
+
+    fn rank1(&self, data: &BitVectorData, pos: usize) -> Option<usize> {
+        self.get_or_build(data).rank1(data, pos)
+    }
+
+    fn rank1_and_access(&self, data: &BitVectorData, pos: usize) -> Option<(usize, bool)> {
+        self.get_or_build(data).rank1_and_access(data, pos)
+    }
+
+    fn rank0(&self, data: &BitVectorData, pos: usize) -> Option<usize> {
+        self.get_or_build(data).rank0(data, pos)
+    }
+
+    fn select1(&self, data: &BitVectorData, k: usize) -> Option<usize> {
+        self.get_or_build(data).select1(data, k)
+    }
+
+    fn select0(&self, data: &BitVectorData, k: usize) -> Option<usize> {
+        self.get_or_build(data).select0(data, k)
+    }
+
+    fn size_in_bytes(&self) -> usize {
+        self.index.get().map_or(0, |index| index.size_in_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bit_vector::{Access, BitVector, NumBits, Rank, Rank9SelIndex, Select};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static NUM_BUILDS: AtomicUsize = AtomicUsize::new(0);
+
+    /// [`Rank9SelIndex`] counting its constructions.
+    struct CountingIndex(Rank9SelIndex);
+
+    impl BitVectorIndex for CountingIndex {
+        fn build(data: &BitVectorData) -> Self {
+            NUM_BUILDS.fetch_add(1, Ordering::SeqCst);
+            Self(Rank9SelIndex::build(data))
+        }
+
+        fn num_ones(&self, data: &BitVectorData) -> usize {
+            BitVectorIndex::num_ones(&self.0, data)
+        }
+
+        fn rank1(&self, data: &BitVectorData, pos: usize) -> Option<usize> {
+            BitVectorIndex::rank1(&self.0, data, pos)
+        }
+
+        fn select1(&self, data: &BitVectorData, k: usize) -> Option<usize> {
+            BitVectorIndex::select1(&self.0, data, k)
+        }
+
+        fn select0(&self, data: &BitVectorData, k: usize) -> Option<usize> {
+            BitVectorIndex::select0(&self.0, data, k)
+        }
+    }
+
+    #[test]
+    fn test_builds_once_on_first_query() {
+        let data = BitVectorData::from_bits((0..1000).map(|i| i % 3 == 0));
+        let bv = BitVector::new(data, LazyIndex::<CountingIndex>::new());
+        assert_eq!(bv.access(3), Some(true));
+        assert_eq!(bv.num_ones(), 334);
+        assert_eq!(bv.index.size_in_bytes(), 0);
+        assert!(!bv.index.is_built());
+        assert_eq!(NUM_BUILDS.load(Ordering::SeqCst), 0);
+
+        assert_eq!(bv.rank1(10), Some(4));
+        assert!(bv.index.is_built());
+        assert_eq!(NUM_BUILDS.load(Ordering::SeqCst), 1);
+        let built = bv.index.get().unwrap() as *const CountingIndex;
+
+        assert_eq!(bv.select1(4), Some(12));
+        assert_eq!(bv.select0(0), Some(1));
+        assert_eq!(bv.rank0(10), Some(6));
+        assert_eq!(NUM_BUILDS.load(Ordering::SeqCst), 1);
+        assert_eq!(bv.index.get().unwrap() as *const CountingIndex, built);
+    }
+
+    #[test]
+    fn test_matches_rank9sel() {
+        let bits: Vec<bool> = (0..3000).map(|i| (i * 7 + i / 3) % 5 < 2).collect();
+        let plain = BitVector::from(BitVectorData::from_bits(bits));
+        let lazy = plain.clone().with_index::<LazyIndex<Rank9SelIndex>>();
+        let eager = plain.with_index::<Rank9SelIndex>();
+        for pos in 0..=eager.num_bits() {
+            assert_eq!(lazy.rank1(pos), eager.rank1(pos));
+        }
+        for k in 0..=eager.num_bits() {
+            assert_eq!(lazy.select1(k), eager.select1(k));
+            assert_eq!(lazy.select0(k), eager.select0(k));
+        }
+        assert_eq!(lazy.index.size_in_bytes(), eager.index.size_in_bytes());
+    }
+
+    #[test]
+    fn test_default_without_index_default() {
+        // CountingIndex implements neither Default nor Clone.
+        let index = LazyIndex::<CountingIndex>::default();
+        assert!(!index.is_built());
+        assert!(index.get().is_none());
+    }
+}
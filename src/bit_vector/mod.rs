@@ -24,6 +24,7 @@
 //! | --- | :-: | :-: | :-: | :-: | :-: |
 //! | [`BitVector`] | $`O(1)`$  | $`O(u)`$ | $`O(u)`$ | $`O(1)`$ | $`u`$ |
 //! | [`BitVector<rank9sel::inner::Rank9SelIndex>`] | $`O(1)`$ | $`O(1)`$ | $`O(\lg u)`$ | -- | $`u + o(u)`$ |
+//! | [`MutableRankBitVector`] | $`O(1)`$ | $`O(\lg u)`$ | $`O(\lg u)`$ | $`O(\lg u)`$ | $`u + o(u)`$ |
 //!
 //! ## Plain bit vectors without index
 //!
@@ -39,6 +40,11 @@
 //! [`BitVector<rank9sel::inner::Rank9SelIndex>`] is an implementation of Vigna's Rank9 and hinted selection techniques, supporting
 //! constant-time Rank and logarithmic-time Select queries.
 //!
+//! ## Mutable bit vectors with index
+//!
+//! [`MutableRankBitVector`] keeps the numbers of ones in blocks in a Fenwick tree,
+//! so bits can be updated between Rank and Select queries without a rebuild.
+//!
 //! # Examples
 //!
 //! This module provides several traits for essential behaviors,
@@ -50,7 +56,7 @@
 //! use jerky::bit_vector::*;
 //!
 //! let mut builder = BitVectorBuilder::new();
-//! builder.extend_bits([true, false, false, true]);
+//! builder.extend_bits([true, false, false, true])?;
 //! let bv = builder.freeze::<Rank9SelIndex>();
 //!
 //! assert_eq!(bv.num_bits(), 4);
@@ -66,7 +72,17 @@
 //! # Ok(())
 //! # }
 //! ```
+pub mod cached_select;
+pub mod chunked_bitmap;
+pub mod data_ref;
+#[cfg(feature = "std")]
+pub mod lazy_index;
+pub mod mutable_rank;
+pub mod nullable_bits;
 pub mod rank9sel;
+pub mod sampled_rank;
+pub mod slice;
+pub mod stats;
 
 /// Interface for building a bit vector with rank/select queries.
 
@@ -119,17 +135,169 @@ pub trait Select {
     fn select0(&self, k: usize) -> Option<usize>;
 }
 
+/// Convenience queries built on [`NumBits`] and [`Select`],
+/// implemented for every type providing both.
+pub trait SelectExt: NumBits + Select {
+    /// Returns the position of the first bit set, or [`None`] if no bit is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::{BitVector, BitVectorData, SelectExt};
+    ///
+    /// let bv = BitVector::from(BitVectorData::from_bits([false, true, true, false]));
+    /// assert_eq!(bv.first_one(), Some(1));
+    /// let bv = BitVector::from(BitVectorData::from_bits([false, false]));
+    /// assert_eq!(bv.first_one(), None);
+    /// ```
+    fn first_one(&self) -> Option<usize> {
+        self.select1(0)
+    }
+
+    /// Returns the position of the first bit unset, or [`None`] if every bit is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::{BitVector, BitVectorData, SelectExt};
+    ///
+    /// let bv = BitVector::from(BitVectorData::from_bits([true, false, true, false]));
+    /// assert_eq!(bv.first_zero(), Some(1));
+    /// let bv = BitVector::from(BitVectorData::from_bits([true, true]));
+    /// assert_eq!(bv.first_zero(), None);
+    /// ```
+    fn first_zero(&self) -> Option<usize> {
+        self.select0(0)
+    }
+
+    /// Returns the position of the last bit set, or [`None`] if no bit is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::{BitVector, BitVectorData, SelectExt};
+    ///
+    /// let bv = BitVector::from(BitVectorData::from_bits([false, true, true, false]));
+    /// assert_eq!(bv.last_one(), Some(2));
+    /// let bv = BitVector::from(BitVectorData::from_bits([false, false]));
+    /// assert_eq!(bv.last_one(), None);
+    /// ```
+    fn last_one(&self) -> Option<usize> {
+        self.select1(self.num_ones().checked_sub(1)?)
+    }
+
+    /// Returns the position of the last bit unset, or [`None`] if every bit is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::{BitVector, BitVectorData, SelectExt};
+    ///
+    /// let bv = BitVector::from(BitVectorData::from_bits([false, true, false, true]));
+    /// assert_eq!(bv.last_zero(), Some(2));
+    /// let bv = BitVector::from(BitVectorData::from_bits([true, true]));
+    /// assert_eq!(bv.last_zero(), None);
+    /// ```
+    fn last_zero(&self) -> Option<usize> {
+        self.select0(self.num_zeros().checked_sub(1)?)
+    }
+}
+
+impl<T: NumBits + Select> SelectExt for T {}
+
+/// Object-safe combination of the bit vector query traits,
+/// implemented for every type providing all of them.
+pub trait RankSelect: Access + Rank + Select + NumBits {}
+
+impl<T: Access + Rank + Select + NumBits> RankSelect for T {}
+
+/// Interface for materializing a bit set into plain [`BitVectorData`],
+/// the canonical representation shared by all bit vectors.
+///
+/// The result has the same number of bits and the same bits set
+/// as the original structure.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::bit_vector::*;
+///
+/// let bits = [true, false, false, true];
+/// let bm = ChunkedBitmap::from_bits(bits)?;
+/// assert_eq!(bm.to_dense(), BitVectorData::from_bits(bits));
+/// # Ok(())
+/// # }
+/// ```
+pub trait ToDense {
+    /// Returns the bits as plain [`BitVectorData`].
+    fn to_dense(&self) -> BitVectorData;
+}
+
+/// Index kinds selectable at runtime with [`build_rank_select`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitVecKind {
+    /// [`BitVector`] with [`NoIndex`].
+    NoIndex,
+    /// [`BitVector`] with [`Rank9SelIndex`].
+    Rank9Sel,
+}
+
+/// Builds a bit vector with the index chosen by `kind` behind a trait object.
+///
+/// # Examples
+///
+/// ```
+/// use jerky::bit_vector::{build_rank_select, BitVecKind};
+///
+/// for kind in [BitVecKind::NoIndex, BitVecKind::Rank9Sel] {
+///     let bv = build_rank_select(kind, [true, false, false, true]);
+///     assert_eq!(bv.num_ones(), 2);
+///     assert_eq!(bv.rank1(4), Some(2));
+///     assert_eq!(bv.select1(1), Some(3));
+/// }
+/// ```
+pub fn build_rank_select<B>(kind: BitVecKind, bits: B) -> Box<dyn RankSelect>
+where
+    B: IntoIterator<Item = bool>,
+{
+    let data = BitVectorData::from_bits(bits);
+    match kind {
+        BitVecKind::NoIndex => Box::new(BitVector::from(data)),
+        BitVecKind::Rank9Sel => {
+            let index: Rank9SelIndex = BitVectorIndex::build(&data);
+            Box::new(BitVector::new(data, index))
+        }
+    }
+}
+
 /// The number of bits in a machine word.
 pub const WORD_LEN: usize = core::mem::size_of::<usize>() * 8;
 
+use core::marker::PhantomData;
+
 use anybytes::{Bytes, View};
 use anyhow::{anyhow, Result};
 
+use crate::int_vectors::IntSequence;
+
 /// Builder that collects raw bits into a zero-copy [`BitVector`].
+///
+/// Freezing or serializing consumes the builder and moves its words into
+/// the resulting [`Bytes`]. Cloning copies the words, so a clone can keep
+/// being modified without affecting vectors frozen from the original, and vice versa.
+///
+/// Bit `i` is stored LSB-first, i.e., in bit `i % WORD_LEN` of word `i / WORD_LEN`.
+/// A builder created by [`Self::new_msb_first`] reads the packed inputs of
+/// [`Self::push_bits`] and [`Self::extend_from_words`] MSB-first instead.
 #[derive(Debug, Default, Clone)]
 pub struct BitVectorBuilder {
     words: Vec<usize>,
     len: usize,
+    max_len: Option<usize>,
+    msb_first: bool,
+    /// Number of bits set, maintained as bits are pushed and set.
+    num_ones: usize,
 }
 
 impl BitVectorBuilder {
@@ -138,6 +306,164 @@ impl BitVectorBuilder {
         Self::default()
     }
 
+    /// Creates an empty builder reserving space for at least `capa` bits.
+    ///
+    /// Unused capacity is released when the builder is frozen or serialized.
+    pub fn with_capacity(capa: usize) -> Self {
+        Self {
+            words: Vec::with_capacity(crate::utils::ceiled_divide(capa, WORD_LEN)),
+            len: 0,
+            max_len: None,
+            msb_first: false,
+            num_ones: 0,
+        }
+    }
+
+    /// Creates an empty builder whose packed inputs are read MSB-first,
+    /// the bit order used by some other libraries and interchange formats.
+    ///
+    /// [`Self::push_bits`] pushes the most significant of its `len` bits first,
+    /// and [`Self::extend_from_words`] takes bit `i` from bit `WORD_LEN - 1 - i % WORD_LEN`
+    /// (counted from the least significant bit) of word `i / WORD_LEN`,
+    /// as [`BitVectorData::from_msb_first_words`] does.
+    /// Single-bit operations are unaffected.
+    ///
+    /// The frozen vector stores the same logical bits in the native LSB-first layout,
+    /// so [`Access`] and the broadword operations work unchanged.
+    /// Use [`BitVectorData::to_msb_first_words`] to write them out MSB-first again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::{BitVectorBuilder, NoIndex};
+    ///
+    /// let mut builder = BitVectorBuilder::new_msb_first();
+    /// builder.push_bits(0b110, 3)?;
+    /// builder.extend_from_words(&[0b01 << 62], 2)?;
+    /// builder.push_bit(true)?;
+    /// let bv = builder.freeze::<NoIndex>();
+    /// assert_eq!(bv.data.to_bit_string(), "110011");
+    /// assert_eq!(bv.data.to_msb_first_words(), vec![0b110011 << 58]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_msb_first() -> Self {
+        Self {
+            msb_first: true,
+            ..Self::default()
+        }
+    }
+
+    /// Creates an empty builder that refuses to grow beyond `max_len` bits.
+    ///
+    /// Pushing bits beyond the limit returns an error instead of growing the
+    /// builder, which guards construction driven by untrusted input.
+    /// The limit does not reserve any memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::BitVectorBuilder;
+    ///
+    /// let mut builder = BitVectorBuilder::with_max_len(2);
+    /// assert!(builder.extend_bits([true, false]).is_ok());
+    /// assert!(builder.push_bit(true).is_err());
+    /// ```
+    pub fn with_max_len(max_len: usize) -> Self {
+        Self {
+            max_len: Some(max_len),
+            ..Self::default()
+        }
+    }
+
+    /// Removes all bits while keeping the allocated capacity for reuse,
+    /// like [`Vec::clear`]. The maximum length, if any, is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::BitVectorBuilder;
+    ///
+    /// let mut builder = BitVectorBuilder::new();
+    /// builder.extend_bits([true, false])?;
+    /// builder.clear();
+    /// builder.push_bit(true)?;
+    /// assert_eq!(builder.into_bytes().0, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clear(&mut self) {
+        self.words.clear();
+        self.len = 0;
+        self.num_ones = 0;
+    }
+
+    /// Returns the number of bits pushed so far.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if no bits have been pushed.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of bits the builder can hold without reallocating.
+    pub fn capacity_bits(&self) -> usize {
+        self.words.capacity() * WORD_LEN
+    }
+
+    /// Reserves space for at least `additional_bits` more bits,
+    /// like [`Vec::reserve`] on the underlying words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::BitVectorBuilder;
+    ///
+    /// let mut builder = BitVectorBuilder::new();
+    /// builder.push_bit(true)?;
+    /// builder.reserve(1000);
+    /// assert!(1001 <= builder.capacity_bits());
+    /// assert_eq!(builder.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn reserve(&mut self, additional_bits: usize) {
+        let additional = self.additional_words(additional_bits);
+        self.words.reserve(additional);
+    }
+
+    /// Reserves space for exactly `additional_bits` more bits rounded up to whole words,
+    /// like [`Vec::reserve_exact`] on the underlying words.
+    pub fn reserve_exact(&mut self, additional_bits: usize) {
+        let additional = self.additional_words(additional_bits);
+        self.words.reserve_exact(additional);
+    }
+
+    /// Returns the number of words to be added for holding `additional_bits` more bits.
+    #[inline(always)]
+    fn additional_words(&self, additional_bits: usize) -> usize {
+        let needed =
+            crate::utils::ceiled_divide(self.len.saturating_add(additional_bits), WORD_LEN);
+        needed - self.words.len()
+    }
+
+    /// Returns an error if pushing `additional` bits exceeds the maximum length.
+    #[inline(always)]
+    fn check_grow(&self, additional: usize) -> Result<()> {
+        match self.max_len {
+            Some(max_len) if max_len - self.len < additional => Err(anyhow!(
+                "len must be no greater than max_len={max_len}, but got {}.",
+                self.len.saturating_add(additional)
+            )),
+            _ => Ok(()),
+        }
+    }
+
     /// Creates a builder that stores `len` copies of `bit`.
     pub fn from_bit(bit: bool, len: usize) -> Self {
         if len == 0 {
@@ -151,11 +477,22 @@ impl BitVectorBuilder {
             let mask = (1 << shift) - 1;
             *words.last_mut().unwrap() &= mask;
         }
-        Self { words, len }
+        Self {
+            words,
+            len,
+            max_len: None,
+            msb_first: false,
+            num_ones: if bit { len } else { 0 },
+        }
     }
 
     /// Pushes a single bit.
-    pub fn push_bit(&mut self, bit: bool) {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the builder would exceed its maximum length.
+    pub fn push_bit(&mut self, bit: bool) -> Result<()> {
+        self.check_grow(1)?;
         let pos_in_word = self.len % WORD_LEN;
         if pos_in_word == 0 {
             self.words.push(bit as usize);
@@ -164,17 +501,34 @@ impl BitVectorBuilder {
             *cur |= (bit as usize) << pos_in_word;
         }
         self.len += 1;
+        self.num_ones += bit as usize;
+        Ok(())
     }
 
     /// Pushes `len` bits from `bits` at the end.
     ///
-    /// Bits outside the lowest `len` bits are truncated.
+    /// Bits outside the lowest `len` bits are truncated. The lowest bit is pushed
+    /// first, or the highest of the `len` bits if the builder is MSB-first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `WORD_LEN < len` or if the builder would exceed
+    /// its maximum length.
     pub fn push_bits(&mut self, bits: usize, len: usize) -> Result<()> {
+        if self.msb_first && len != 0 && len <= WORD_LEN {
+            return self.push_lsb_first_bits((bits << (WORD_LEN - len)).reverse_bits(), len);
+        }
+        self.push_lsb_first_bits(bits, len)
+    }
+
+    /// Pushes `len` bits from `bits` at the end, the lowest bit first.
+    fn push_lsb_first_bits(&mut self, bits: usize, len: usize) -> Result<()> {
         if WORD_LEN < len {
             return Err(anyhow!(
                 "len must be no greater than {WORD_LEN}, but got {len}."
             ));
         }
+        self.check_grow(len)?;
         if len == 0 {
             return Ok(());
         }
@@ -185,6 +539,7 @@ impl BitVectorBuilder {
             usize::MAX
         };
         let bits = bits & mask;
+        self.num_ones += bits.count_ones() as usize;
 
         let pos_in_word = self.len % WORD_LEN;
         if pos_in_word == 0 {
@@ -200,6 +555,12 @@ impl BitVectorBuilder {
         Ok(())
     }
 
+    /// Returns the `len` bits pushed at position `pos`, or [`None`] if
+    /// `WORD_LEN < len` or the range `pos..pos + len` is out of bounds.
+    pub(crate) fn get_bits(&self, pos: usize, len: usize) -> Option<usize> {
+        get_bits_in(&self.words, self.len, pos, len)
+    }
+
     /// Sets the `pos`-th bit to `bit`.
     pub fn set_bit(&mut self, pos: usize, bit: bool) -> Result<()> {
         if self.len <= pos {
@@ -210,17 +571,140 @@ impl BitVectorBuilder {
         }
         let word = pos / WORD_LEN;
         let pos_in_word = pos % WORD_LEN;
+        self.num_ones -= (self.words[word] >> pos_in_word) & 1;
+        self.num_ones += bit as usize;
         self.words[word] &= !(1 << pos_in_word);
         self.words[word] |= (bit as usize) << pos_in_word;
         Ok(())
     }
 
+    /// Sets the bit at each position to the paired value of `updates`, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error at the first position not less than `self.len()`.
+    /// The updates preceding it have already been applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::{BitVectorBuilder, NoIndex};
+    ///
+    /// let mut builder = BitVectorBuilder::from_bit(false, 4);
+    /// builder.set_bits([(0, true), (3, true), (0, false)])?;
+    /// assert!(builder.set_bits([(1, true), (4, true)]).is_err());
+    /// let bv = builder.freeze::<NoIndex>();
+    /// assert_eq!(bv.data.to_bit_string(), "0101");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_bits<I>(&mut self, updates: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (usize, bool)>,
+    {
+        updates
+            .into_iter()
+            .try_for_each(|(pos, bit)| self.set_bit(pos, bit))
+    }
+
     /// Extends the builder from an iterator of bits.
-    pub fn extend_bits<I: IntoIterator<Item = bool>>(&mut self, bits: I) {
-        bits.into_iter().for_each(|b| self.push_bit(b));
+    ///
+    /// Space for the lower bound of the iterator's size hint is reserved up front,
+    /// capped at the maximum length if any. A hint too large to allocate is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the builder would exceed its maximum length.
+    /// The bits preceding the offending one are kept.
+    pub fn extend_bits<I: IntoIterator<Item = bool>>(&mut self, bits: I) -> Result<()> {
+        let mut bits = bits.into_iter();
+        let lower = bits.size_hint().0;
+        let additional = self.additional_words(
+            self.max_len
+                .map_or(lower, |max_len| lower.min(max_len - self.len)),
+        );
+        // A huge hint, e.g., of `0..usize::MAX`, cannot be reserved,
+        // in which case the words grow on demand.
+        let _ = self.words.try_reserve(additional);
+        bits.try_for_each(|b| self.push_bit(b))
+    }
+
+    /// Appends the first `bit_len` bits packed into `words`,
+    /// which are read MSB-first if the builder is MSB-first.
+    ///
+    /// If the builder ends on a word boundary, the words are copied directly
+    /// and only the last partial word is masked. Otherwise, each word is shifted
+    /// into place with [`Self::push_bits`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `words` holds fewer than `bit_len` bits
+    /// or if the builder would exceed its maximum length.
+    /// Nothing is appended in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::BitVectorBuilder;
+    ///
+    /// let mut builder = BitVectorBuilder::new();
+    /// builder.extend_from_words(&[0b1101], 3)?;
+    /// builder.extend_from_words(&[0b11], 2)?;
+    /// assert_eq!(builder.len(), 5);
+    /// assert_eq!(builder.into_bytes().1.as_ref()[0], 0b11101);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn extend_from_words(&mut self, words: &[usize], bit_len: usize) -> Result<()> {
+        let num_words = crate::utils::ceiled_divide(bit_len, WORD_LEN);
+        if words.len() < num_words {
+            return Err(anyhow!(
+                "words must hold at least bit_len={bit_len} bits, but got {} bits.",
+                words.len().saturating_mul(WORD_LEN)
+            ));
+        }
+        self.check_grow(bit_len)?;
+        let msb_first = self.msb_first;
+        let word = |&w: &usize| if msb_first { w.reverse_bits() } else { w };
+        let rest = bit_len % WORD_LEN;
+        if self.len % WORD_LEN == 0 {
+            let start = self.words.len();
+            self.words.extend(words[..num_words].iter().map(word));
+            if rest != 0 {
+                *self.words.last_mut().unwrap() &= (1 << rest) - 1;
+            }
+            self.num_ones += self.words[start..]
+                .iter()
+                .map(|w| w.count_ones() as usize)
+                .sum::<usize>();
+            self.len += bit_len;
+        } else {
+            for w in &words[..bit_len / WORD_LEN] {
+                self.push_lsb_first_bits(word(w), WORD_LEN)?;
+            }
+            if rest != 0 {
+                self.push_lsb_first_bits(word(&words[num_words - 1]), rest)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Pushes the bits of `data` in positions `start..end`, word by word.
+    fn push_data_range(&mut self, data: &BitVectorData, start: usize, end: usize) {
+        let mut pos = start;
+        while pos < end {
+            let len = (end - pos).min(WORD_LEN);
+            self.push_lsb_first_bits(data.get_bits(pos, len).unwrap(), len)
+                .unwrap();
+            pos += len;
+        }
     }
 
-    fn into_data(self) -> BitVectorData {
+    /// Moves the bits into [`BitVectorData`] without building an index.
+    pub(crate) fn into_data(mut self) -> BitVectorData {
+        self.words.shrink_to_fit();
         let words = Bytes::from_source(self.words).view::<[usize]>().unwrap();
         BitVectorData {
             words,
@@ -235,10 +719,182 @@ impl BitVectorBuilder {
         BitVector::new(data, index)
     }
 
+    /// Finalizes the builder into a [`BitVector`] together with [`BuildStats`].
+    ///
+    /// The number of ones is counted as bits are pushed and set,
+    /// so the data is not walked again after the index is built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::*;
+    ///
+    /// let mut builder = BitVectorBuilder::new();
+    /// builder.extend_bits([true, false, true])?;
+    /// let (bv, stats) = builder.freeze_with_stats::<Rank9SelIndex>();
+    ///
+    /// assert_eq!(stats.num_ones, 2);
+    /// assert_eq!(stats.num_words, 1);
+    /// assert_eq!(stats.index_bytes, bv.index.to_bytes().len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn freeze_with_stats<I: BitVectorIndex>(self) -> (BitVector<I>, BuildStats) {
+        let num_ones = self.num_ones;
+        let bv = self.freeze::<I>();
+        let stats = BuildStats {
+            num_ones,
+            num_words: bv.data.num_words(),
+            index_bytes: bv.index.size_in_bytes(),
+        };
+        (bv, stats)
+    }
+
+    /// Finalizes the builder into a [`BitVector`] whose words start at a
+    /// multiple of `align` bytes.
+    ///
+    /// The words are copied into a fresh buffer padded so that
+    /// `data.words().as_ptr() as usize % align == 0` holds, which allows
+    /// aligned SIMD loads over the words. Alignments not exceeding the size of
+    /// a machine word are satisfied without padding.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `align` is not a power of two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::*;
+    ///
+    /// let mut builder = BitVectorBuilder::new();
+    /// builder.extend_bits([true, false, true])?;
+    /// let bv = builder.freeze_aligned::<NoIndex>(64)?;
+    ///
+    /// assert_eq!(bv.data.words().as_ptr() as usize % 64, 0);
+    /// assert_eq!(bv.num_ones(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn freeze_aligned<I: BitVectorIndex>(self, align: usize) -> Result<BitVector<I>> {
+        if !align.is_power_of_two() {
+            return Err(anyhow!("align must be a power of two, but got {align}."));
+        }
+        let word_size = core::mem::size_of::<usize>();
+        let num_words = self.words.len();
+        let pad = align.saturating_sub(word_size) / word_size;
+        let mut buf = vec![0usize; num_words + pad];
+        let addr = buf.as_ptr() as usize;
+        let offset = (align - addr % align) % align / word_size;
+        buf[offset..offset + num_words].copy_from_slice(&self.words);
+
+        // Moving `buf` into `Bytes` keeps its heap allocation in place.
+        let bytes = Bytes::from_source(buf);
+        let start = offset * word_size;
+        let end = start + num_words * word_size;
+        let words = bytes
+            .slice_to_bytes(&bytes.as_ref()[start..end])
+            .ok_or_else(|| anyhow!("invalid slice"))?
+            .view::<[usize]>()
+            .map_err(|e| anyhow!(e))?;
+        let data = BitVectorData {
+            words,
+            len: self.len,
+        };
+        let index = I::build(&data);
+        Ok(BitVector::new(data, index))
+    }
+
     /// Serializes the builder contents into a [`Bytes`] buffer.
-    pub fn into_bytes(self) -> (usize, Bytes) {
+    pub fn into_bytes(mut self) -> (usize, Bytes) {
+        self.words.shrink_to_fit();
         (self.len, Bytes::from_source(self.words))
     }
+
+    /// Fixes the index type built on freezing,
+    /// so that the builder can be finalized through [`Freeze`](crate::Freeze).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::*;
+    /// use jerky::Freeze;
+    ///
+    /// let mut builder = BitVectorBuilder::new();
+    /// builder.extend_bits([true, false, true])?;
+    /// let bv = builder.indexed::<Rank9SelIndex>().freeze();
+    ///
+    /// assert_eq!(bv.rank1(3), Some(2));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn indexed<I: BitVectorIndex>(self) -> IndexedBitVectorBuilder<I> {
+        IndexedBitVectorBuilder {
+            builder: self,
+            _index: PhantomData,
+        }
+    }
+}
+
+/// [`BitVectorBuilder`] bound to the index type `I`,
+/// created by [`BitVectorBuilder::indexed`].
+#[derive(Debug, Clone)]
+pub struct IndexedBitVectorBuilder<I> {
+    builder: BitVectorBuilder,
+    _index: PhantomData<I>,
+}
+
+impl<I: BitVectorIndex> IndexedBitVectorBuilder<I> {
+    /// Returns the wrapped builder.
+    pub fn into_inner(self) -> BitVectorBuilder {
+        self.builder
+    }
+
+    /// Finalizes the builder into a [`BitVector`] with index `I`.
+    pub fn freeze(self) -> BitVector<I> {
+        self.builder.freeze::<I>()
+    }
+}
+
+/// Returns `len` bits starting at position `pos` of the `num_bits` bits in `words`,
+/// or [`None`] if `WORD_LEN < len` or the range is out of bounds.
+fn get_bits_in(words: &[usize], num_bits: usize, pos: usize, len: usize) -> Option<usize> {
+    let end = pos.checked_add(len)?;
+    if WORD_LEN < len || num_bits < end {
+        return None;
+    }
+    if len == 0 {
+        return Some(0);
+    }
+    let block = pos / WORD_LEN;
+    let shift = pos % WORD_LEN;
+    let mask = if len < WORD_LEN {
+        (1 << len) - 1
+    } else {
+        usize::MAX
+    };
+    let lo = *words.get(block)?;
+    let bits = if shift + len <= WORD_LEN {
+        (lo >> shift) & mask
+    } else {
+        let hi = *words.get(block.checked_add(1)?)?;
+        (lo >> shift) | ((hi << (WORD_LEN - shift)) & mask)
+    };
+    Some(bits)
+}
+
+/// Statistics reported by [`BitVectorBuilder::freeze_with_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildStats {
+    /// Number of bits set.
+    pub num_ones: usize,
+    /// Number of machine words storing the bits.
+    pub num_words: usize,
+    /// Number of bytes occupied by the index.
+    pub index_bytes: usize,
 }
 
 /// Immutable bit vector data without auxiliary indexes.
@@ -261,69 +917,889 @@ impl Default for BitVectorData {
 
 impl BitVectorData {
     /// Creates bit vector data from a bit iterator.
+    ///
+    /// The words are preallocated from the lower bound of the iterator's size hint.
     pub fn from_bits<I: IntoIterator<Item = bool>>(bits: I) -> Self {
         let mut builder = BitVectorBuilder::new();
-        builder.extend_bits(bits);
+        builder.extend_bits(bits).unwrap();
         builder.into_data()
     }
 
-    /// Reconstructs the data from zero-copy [`Bytes`].
-    pub fn from_bytes(len: usize, bytes: Bytes) -> Result<Self> {
-        let words = bytes.view::<[usize]>().map_err(|e| anyhow::anyhow!(e))?;
-        Ok(Self { words, len })
+    /// Creates bit vector data of exactly `len` bits from a bit iterator.
+    ///
+    /// If the iterator yields fewer than `len` bits, the rest are unset;
+    /// if it yields more, it is not consumed past the `len`-th bit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let short = BitVectorData::from_bits_sized(5, [true, true]);
+    /// assert_eq!(short.to_bit_string(), "11000");
+    ///
+    /// let long = BitVectorData::from_bits_sized(2, [true, false, true]);
+    /// assert_eq!(long.to_bit_string(), "10");
+    /// ```
+    pub fn from_bits_sized<I: IntoIterator<Item = bool>>(len: usize, bits: I) -> Self {
+        let padded = bits.into_iter().chain(core::iter::repeat(false)).take(len);
+        Self::from_bits(padded)
     }
 
-    /// Returns the number of bits stored.
-    pub const fn len(&self) -> usize {
-        self.len
+    /// Creates bit vector data of length `universe`
+    /// with the bits at the sorted `positions` set.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `positions` is not strictly increasing
+    /// or contains a position not less than `universe`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::BitVectorData;
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let positions = CompactVector::from_slice(&[0, 2, 3])?;
+    /// let data = BitVectorData::from_sorted_positions(5, &positions)?;
+    /// assert_eq!(data.to_bit_string(), "10110");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_sorted_positions<S: IntSequence>(universe: usize, positions: &S) -> Result<Self> {
+        let mut builder = BitVectorBuilder::from_bit(false, universe);
+        let mut prev = None;
+        for pos in IntSequence::iter(positions) {
+            if universe <= pos {
+                return Err(anyhow!(
+                    "positions must be less than universe={universe}, but got {pos}."
+                ));
+            }
+            if let Some(prev) = prev.filter(|&prev| pos <= prev) {
+                return Err(anyhow!(
+                    "positions must be strictly increasing, but got {pos} after {prev}."
+                ));
+            }
+            builder.set_bit(pos, true)?;
+            prev = Some(pos);
+        }
+        Ok(builder.into_data())
     }
 
-    /// Returns the raw word slice.
-    pub fn words(&self) -> &[usize] {
-        self.words.as_ref()
+    /// Creates bit vector data of length `universe`
+    /// with the bits in the half-open ranges `a..b` set.
+    ///
+    /// Each range is filled word by word, so the cost depends on the number of
+    /// words covered rather than the number of set bits.
+    /// Empty ranges are allowed, and adjacent ranges may touch.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if a range has `b < a` or `universe < b`,
+    /// or if the ranges are not sorted and disjoint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let data = BitVectorData::from_ranges(8, [(1, 3), (3, 4), (6, 8)])?;
+    /// assert_eq!(data.to_bit_string(), "01110011");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_ranges<I>(universe: usize, ranges: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        let mut words = vec![0; crate::utils::ceiled_divide(universe, WORD_LEN)];
+        let mut prev_end = 0;
+        for (a, b) in ranges {
+            if b < a || universe < b {
+                return Err(anyhow!(
+                    "ranges must be within 0..universe={universe}, but got {a}..{b}."
+                ));
+            }
+            if a < prev_end {
+                return Err(anyhow!(
+                    "ranges must be sorted and disjoint, but got {a}..{b} after ..{prev_end}."
+                ));
+            }
+            prev_end = b;
+            if a == b {
+                continue;
+            }
+            let (first, last) = (a / WORD_LEN, (b - 1) / WORD_LEN);
+            let lo_mask = !0 << (a % WORD_LEN);
+            let hi_mask = !0 >> (WORD_LEN - 1 - (b - 1) % WORD_LEN);
+            if first == last {
+                words[first] |= lo_mask & hi_mask;
+            } else {
+                words[first] |= lo_mask;
+                words[first + 1..last].iter_mut().for_each(|w| *w = !0);
+                words[last] |= hi_mask;
+            }
+        }
+        Self::from_words(words, universe)
     }
 
-    /// Returns the number of words stored.
-    pub fn num_words(&self) -> usize {
-        self.words.len()
+    /// Creates a borrowed view of the bits in `lo..hi`,
+    /// or returns [`None`] if `hi < lo` or `self.len() < hi`.
+    ///
+    /// See [`BitVectorSlice`] for the queries supported on the view.
+    pub const fn slice(&self, lo: usize, hi: usize) -> Option<BitVectorSlice<'_>> {
+        BitVectorSlice::new(self, lo, hi)
     }
 
-    /// Returns `len` bits starting at position `pos`.
-    pub fn get_bits(&self, pos: usize, len: usize) -> Option<usize> {
-        if WORD_LEN < len || self.len() < pos + len {
-            return None;
-        }
-        if len == 0 {
-            return Some(0);
-        }
-        let block = pos / WORD_LEN;
-        let shift = pos % WORD_LEN;
-        let mask = if len < WORD_LEN {
-            (1 << len) - 1
-        } else {
-            usize::MAX
-        };
-        let bits = if shift + len <= WORD_LEN {
-            (self.words[block] >> shift) & mask
-        } else {
-            (self.words[block] >> shift) | ((self.words[block + 1] << (WORD_LEN - shift)) & mask)
-        };
-        Some(bits)
+    /// Creates an iterator over the positions of set bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let data = BitVectorData::from_bits([true, false, false, true]);
+    /// assert_eq!(data.ones().collect::<Vec<_>>(), vec![0, 3]);
+    /// ```
+    pub fn ones(&self) -> Ones<'_> {
+        self.ones_in_range(0, self.len)
     }
 
-    /// Serializes the data into a [`Bytes`] buffer.
-    pub fn to_bytes(&self) -> (usize, Bytes) {
-        (self.len, self.words.clone().bytes())
+    /// Counts the ones in every window of `w` consecutive bits.
+    ///
+    /// The `i`-th count is the number of ones in `i..i + w`, so the output holds
+    /// `len - w + 1` counts, or none if `len < w`. All counts are computed in
+    /// one pass by adding the bit entering the window and removing the bit leaving it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let data = BitVectorData::from_bit_string("110101")?;
+    /// assert_eq!(data.windowed_popcounts(3), vec![2, 2, 1, 2]);
+    /// assert!(data.windowed_popcounts(7).is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn windowed_popcounts(&self, w: usize) -> Vec<usize> {
+        if self.len < w {
+            return vec![];
+        }
+        let words = self.words();
+        let bit = |pos: usize| (words[pos / WORD_LEN] >> (pos % WORD_LEN)) & 1;
+        let mut count = (0..w).map(bit).sum();
+        let mut counts = Vec::with_capacity(self.len - w + 1);
+        counts.push(count);
+        for pos in w..self.len {
+            count = count + bit(pos) - bit(pos - w);
+            counts.push(count);
+        }
+        counts
     }
-}
 
-impl From<BitVectorData> for BitVector<NoIndex> {
-    fn from(data: BitVectorData) -> Self {
-        BitVector::new(data, NoIndex)
+    /// Calls `f` with the position of each set bit in increasing order.
+    ///
+    /// This is the callback form of [`Self::ones`], extracting the set bits
+    /// word by word without per-position bounds checks or iterator state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let mask = BitVectorData::from_bits([true, false, true, true]);
+    /// let vals = [10, 20, 30, 40];
+    /// let mut kept = vec![];
+    /// mask.for_each_one(|i| kept.push(vals[i]));
+    /// assert_eq!(kept, vec![10, 30, 40]);
+    /// ```
+    pub fn for_each_one(&self, mut f: impl FnMut(usize)) {
+        let num_words = crate::utils::ceiled_divide(self.len, WORD_LEN);
+        let rem = self.len % WORD_LEN;
+        for (wpos, &w) in self.words[..num_words].iter().enumerate() {
+            let mut cur = if wpos + 1 == num_words && rem != 0 {
+                w & ((1 << rem) - 1)
+            } else {
+                w
+            };
+            while cur != 0 {
+                f(wpos * WORD_LEN + cur.trailing_zeros() as usize);
+                cur &= cur - 1;
+            }
+        }
     }
-}
 
-impl Access for BitVectorData {
+    /// Creates an iterator over the positions of set bits in `lo..hi`.
+    ///
+    /// `hi` is clamped to `self.len()`, and the iterator is empty if `hi <= lo`.
+    /// Only the words overlapping the range are visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let data = BitVectorData::from_bits([true, true, false, true, true]);
+    /// assert_eq!(data.ones_in_range(1, 4).collect::<Vec<_>>(), vec![1, 3]);
+    /// assert_eq!(data.ones_in_range(3, 9).collect::<Vec<_>>(), vec![3, 4]);
+    /// assert_eq!(data.ones_in_range(4, 2).next(), None);
+    /// ```
+    pub fn ones_in_range(&self, lo: usize, hi: usize) -> Ones<'_> {
+        let words = self.words();
+        let hi = hi.min(self.len);
+        if hi <= lo {
+            return Ones {
+                words,
+                wpos: 0,
+                cur: 0,
+                hi: 0,
+            };
+        }
+        let wpos = lo / WORD_LEN;
+        Ones {
+            words,
+            wpos,
+            cur: words[wpos] & (usize::MAX << (lo % WORD_LEN)),
+            hi,
+        }
+    }
+
+    /// Checks if any bit in `lo..hi` is set.
+    ///
+    /// `hi` is clamped to `self.len()`, and the range is empty if `hi <= lo`.
+    /// Words are scanned from `lo` and the scan stops at the first set bit,
+    /// so no rank has to be computed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let data = BitVectorData::from_bits((0..200).map(|i| i == 130));
+    /// assert!(data.any_ones_in(100, 131));
+    /// assert!(!data.any_ones_in(0, 130));
+    /// assert!(!data.any_ones_in(131, 200));
+    /// ```
+    pub fn any_ones_in(&self, lo: usize, hi: usize) -> bool {
+        self.ones_in_range(lo, hi).next().is_some()
+    }
+
+    /// Creates bit vector data from a string of `'0'` and `'1'`,
+    /// where the first character is position 0.
+    ///
+    /// Whitespace and underscores are ignored so that long fixtures can be grouped.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `s` contains any other character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::{Access, BitVectorData};
+    ///
+    /// let data = BitVectorData::from_bit_string("1011")?;
+    /// assert_eq!(data.len(), 4);
+    /// assert_eq!(data.access(0), Some(true));
+    /// assert_eq!(data.access(1), Some(false));
+    /// assert_eq!(BitVectorData::from_bit_string("10_11")?, data);
+    /// assert!(BitVectorData::from_bit_string("1021").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_bit_string(s: &str) -> Result<Self> {
+        let mut builder = BitVectorBuilder::new();
+        for c in s.chars() {
+            match c {
+                '0' => builder.push_bit(false)?,
+                '1' => builder.push_bit(true)?,
+                '_' => {}
+                c if c.is_whitespace() => {}
+                c => {
+                    return Err(anyhow!(
+                        "s must consist only of '0', '1', '_', and whitespace, but got {c:?}."
+                    ))
+                }
+            }
+        }
+        Ok(builder.into_data())
+    }
+
+    /// Formats the bits as a string of `'0'` and `'1'`,
+    /// where the first character is position 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let data = BitVectorData::from_bits([true, false, true, true]);
+    /// assert_eq!(data.to_bit_string(), "1011");
+    /// ```
+    pub fn to_bit_string(&self) -> String {
+        (0..self.len)
+            .map(|pos| if self.access(pos).unwrap() { '1' } else { '0' })
+            .collect()
+    }
+
+    /// Creates bit vector data by interleaving two bit streams of equal length.
+    ///
+    /// The `i`-th bit of `a` is stored at position `2 * i`
+    /// and the `i`-th bit of `b` at position `2 * i + 1`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `a` and `b` yield different numbers of bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::{Access, BitVectorData};
+    ///
+    /// let data = BitVectorData::interleave([true, true], [false, true])?;
+    /// assert_eq!(data.len(), 4);
+    /// assert_eq!(data.access(0), Some(true));
+    /// assert_eq!(data.access(1), Some(false));
+    /// assert_eq!(data.access(2), Some(true));
+    /// assert_eq!(data.access(3), Some(true));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn interleave<A, B>(a: A, b: B) -> Result<Self>
+    where
+        A: IntoIterator<Item = bool>,
+        B: IntoIterator<Item = bool>,
+    {
+        let mut a = a.into_iter();
+        let mut b = b.into_iter();
+        let mut builder = BitVectorBuilder::new();
+        let mut len = 0;
+        loop {
+            match (a.next(), b.next()) {
+                (Some(x), Some(y)) => {
+                    builder.push_bit(x)?;
+                    builder.push_bit(y)?;
+                    len += 1;
+                }
+                (None, None) => break,
+                (x, y) => {
+                    let len_a = len + usize::from(x.is_some()) + a.count();
+                    let len_b = len + usize::from(y.is_some()) + b.count();
+                    return Err(anyhow!(
+                        "a and b must have the same length, but got {len_a} and {len_b}."
+                    ));
+                }
+            }
+        }
+        Ok(builder.into_data())
+    }
+
+    /// Creates bit vector data from `len` bits already packed into `words`.
+    ///
+    /// The vector is taken over without copying. Words beyond those needed
+    /// for `len` bits are dropped and unused bits in the last word are cleared.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `words` holds fewer than `len` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::{Access, BitVectorData};
+    ///
+    /// let data = BitVectorData::from_words(vec![0b1101], 3)?;
+    /// assert_eq!(data.len(), 3);
+    /// assert_eq!(data.access(0), Some(true));
+    /// assert_eq!(data.access(1), Some(false));
+    /// assert_eq!(data.words(), &[0b101]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_words(mut words: Vec<usize>, len: usize) -> Result<Self> {
+        let num_words = crate::utils::ceiled_divide(len, WORD_LEN);
+        if words.len() < num_words {
+            return Err(anyhow!(
+                "words must hold at least len={len} bits, but got {} bits.",
+                words.len().saturating_mul(WORD_LEN)
+            ));
+        }
+        words.truncate(num_words);
+        let rest = len % WORD_LEN;
+        if rest != 0 {
+            if let Some(last) = words.last_mut() {
+                *last &= (1 << rest) - 1;
+            }
+        }
+        let words = Bytes::from_source(words).view::<[usize]>().unwrap();
+        Ok(Self { words, len })
+    }
+
+    /// Creates bit vector data from `len` bits packed MSB-first into `words`,
+    /// the layout used by some other libraries and interchange formats.
+    ///
+    /// In the MSB-first layout, bit `i` is bit `WORD_LEN - 1 - i % WORD_LEN`
+    /// (counted from the least significant bit) of word `i / WORD_LEN`,
+    /// whereas this crate stores it in bit `i % WORD_LEN`.
+    /// The bits of each word are reversed on construction,
+    /// so queries such as [`Access`] and [`Self::get_bits`] see the same logical bits
+    /// and the broadword operations keep working on the native layout.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `words` holds fewer than `len` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let data = BitVectorData::from_msb_first_words(vec![0b101 << 61], 3)?;
+    /// assert_eq!(data.to_bit_string(), "101");
+    /// assert_eq!(data.words(), &[0b101]);
+    /// assert_eq!(data.to_msb_first_words(), vec![0b101 << 61]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_msb_first_words(mut words: Vec<usize>, len: usize) -> Result<Self> {
+        for w in words.iter_mut() {
+            *w = w.reverse_bits();
+        }
+        Self::from_words(words, len)
+    }
+
+    /// Returns the bits packed MSB-first into words,
+    /// the inverse of [`Self::from_msb_first_words`].
+    ///
+    /// Bits beyond `len` in the last word are unset.
+    pub fn to_msb_first_words(&self) -> Vec<usize> {
+        let num_words = (self.len + WORD_LEN - 1) / WORD_LEN;
+        let mut words: Vec<usize> = self.words[..num_words]
+            .iter()
+            .map(|w| w.reverse_bits())
+            .collect();
+        let rest = self.len % WORD_LEN;
+        if rest != 0 {
+            if let Some(last) = words.last_mut() {
+                *last &= usize::MAX << (WORD_LEN - rest);
+            }
+        }
+        words
+    }
+
+    /// Computes the set difference `self & !other` word by word,
+    /// i.e., the positions set in `self` but not in `other`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `self` and `other` have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let a = BitVectorData::from_bit_string("1101")?;
+    /// let b = BitVectorData::from_bit_string("0111")?;
+    /// assert_eq!(a.andnot(&b)?.to_bit_string(), "1000");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn andnot(&self, other: &BitVectorData) -> Result<Self> {
+        if self.len != other.len {
+            return Err(anyhow!(
+                "self and other must have the same length, but got {} and {}.",
+                self.len,
+                other.len
+            ));
+        }
+        let num_words = (self.len + WORD_LEN - 1) / WORD_LEN;
+        let words = self.words[..num_words]
+            .iter()
+            .zip(&other.words[..num_words])
+            .map(|(&x, &y)| x & !y)
+            .collect();
+        Self::from_words(words, self.len)
+    }
+
+    /// Sums `f(word)` over the words holding the `len` bits.
+    ///
+    /// This generalizes popcount-based counting to custom per-word reductions.
+    /// The final word is masked to its bits below `len` before `f` is applied,
+    /// and words past it, e.g., alignment padding, are skipped.
+    /// Note that the masked-out bits are unset, so a reduction such as
+    /// `count_zeros` also counts them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let bv = BitVectorData::from_bit_string("0110111")?;
+    /// assert_eq!(bv.count_words_where(|w| w.count_ones()), 5);
+    /// // Adjacent `11` pairs within each word.
+    /// assert_eq!(bv.count_words_where(|w| (w & (w >> 1)).count_ones()), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn count_words_where(&self, f: impl Fn(usize) -> u32) -> usize {
+        let num_words = (self.len + WORD_LEN - 1) / WORD_LEN;
+        let words = &self.words[..num_words];
+        let rem = self.len % WORD_LEN;
+        words
+            .iter()
+            .enumerate()
+            .map(|(i, &w)| {
+                let w = if i + 1 == num_words && rem != 0 {
+                    w & ((1 << rem) - 1)
+                } else {
+                    w
+                };
+                f(w) as usize
+            })
+            .sum()
+    }
+
+    /// Reconstructs the data from zero-copy [`Bytes`].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `bytes` cannot be viewed as machine words
+    /// or holds fewer than `len` bits.
+    pub fn from_bytes(len: usize, bytes: Bytes) -> Result<Self> {
+        let words = bytes.view::<[usize]>().map_err(|e| anyhow::anyhow!(e))?;
+        let num_bits = words.len().saturating_mul(WORD_LEN);
+        if num_bits < len {
+            return Err(anyhow!(
+                "bytes must hold at least len={len} bits, but got {num_bits} bits."
+            ));
+        }
+        Ok(Self { words, len })
+    }
+
+    /// Checks the internal invariants, e.g., of data loaded from untrusted bytes.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `words` holds fewer than `len` bits
+    /// or any bit from `len` on is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let data = BitVectorData::from_bits([true, false, true]);
+    /// data.validate()?;
+    ///
+    /// let (_, bytes) = data.to_bytes();
+    /// let truncated = BitVectorData::from_bytes(2, bytes)?;
+    /// assert!(truncated.validate().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        let num_bits = self.words.len().saturating_mul(WORD_LEN);
+        if num_bits < self.len {
+            return Err(anyhow!(
+                "words must hold at least len={} bits, but got {num_bits} bits.",
+                self.len
+            ));
+        }
+        if !self.padding_is_clear() {
+            return Err(anyhow!(
+                "bits from len={} on must be unset, but got a set bit.",
+                self.len
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the number of bits stored.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the data is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Wraps a bitmap packed into bytes, LSB first, as bit vector data.
+    ///
+    /// Bit `i` is bit `i % 8` (counted from the least significant bit)
+    /// of byte `i / 8`. On little-endian targets this coincides with
+    /// the layout of the words, so `bytes` is viewed without copying if it is
+    /// aligned to and a multiple of the word size, and all bits from `len` on are unset.
+    /// Otherwise, the bits are repacked into a new word buffer
+    /// and the bits from `len` on are dropped.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `bytes` holds fewer than `len` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use anybytes::Bytes;
+    /// use jerky::bit_vector::{Access, BitVectorData};
+    ///
+    /// let bytes = Bytes::from_source(vec![0b0000_0101u8, 0b1000_0000]);
+    /// let data = BitVectorData::from_packed_bytes(bytes, 16)?;
+    /// assert_eq!(data.access(0), Some(true));
+    /// assert_eq!(data.access(1), Some(false));
+    /// assert_eq!(data.access(2), Some(true));
+    /// assert_eq!(data.access(15), Some(true));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_packed_bytes(bytes: Bytes, len: usize) -> Result<Self> {
+        let slice: &[u8] = bytes.as_ref();
+        let num_bits = slice.len().saturating_mul(8);
+        if num_bits < len {
+            return Err(anyhow!(
+                "bytes must hold at least len={len} bits, but got {num_bits} bits."
+            ));
+        }
+        if cfg!(target_endian = "little") {
+            if let Ok(words) = bytes.clone().view::<[usize]>() {
+                let data = Self { words, len };
+                if data.padding_is_clear() {
+                    return Ok(data);
+                }
+            }
+        }
+        let words = slice
+            .chunks(WORD_LEN / 8)
+            .map(|chunk| {
+                let mut buf = [0; WORD_LEN / 8];
+                buf[..chunk.len()].copy_from_slice(chunk);
+                usize::from_le_bytes(buf)
+            })
+            .collect();
+        Self::from_words(words, len)
+    }
+
+    /// Checks if all bits from `len` on are unset.
+    fn padding_is_clear(&self) -> bool {
+        let (wpos, left) = (self.len / WORD_LEN, self.len % WORD_LEN);
+        let words = self.words();
+        let tail = if left == 0 {
+            &words[wpos..]
+        } else {
+            if words[wpos] >> left != 0 {
+                return false;
+            }
+            &words[wpos + 1..]
+        };
+        tail.iter().all(|&w| w == 0)
+    }
+
+    /// Returns the raw word slice.
+    pub fn words(&self) -> &[usize] {
+        self.words.as_ref()
+    }
+
+    /// Borrows the data as a [`BitVectorDataRef`].
+    pub fn as_data_ref(&self) -> BitVectorDataRef<'_> {
+        BitVectorDataRef::from_parts(self.words(), self.len)
+    }
+
+    /// Returns the number of words stored.
+    pub fn num_words(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns `len` bits starting at position `pos`.
+    ///
+    /// Returns [`None`] if `WORD_LEN < len` or the range `pos..pos + len`
+    /// is out of bounds, including when `pos + len` overflows.
+    pub fn get_bits(&self, pos: usize, len: usize) -> Option<usize> {
+        get_bits_in(self.words(), self.len, pos, len)
+    }
+
+    /// Returns the `pos`-th bit together with the length of the maximal run
+    /// of identical bits starting at `pos`, or [`None`] if out of bounds.
+    ///
+    /// The run is located word by word, skipping whole words of identical bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let data = BitVectorData::from_bits([true, true, false, false, false, true]);
+    /// assert_eq!(data.run_length_from(0), Some((true, 2)));
+    /// assert_eq!(data.run_length_from(3), Some((false, 2)));
+    /// assert_eq!(data.run_length_from(5), Some((true, 1)));
+    /// assert_eq!(data.run_length_from(6), None);
+    /// ```
+    pub fn run_length_from(&self, pos: usize) -> Option<(bool, usize)> {
+        let bit = self.access(pos)?;
+        let mut wpos = pos / WORD_LEN;
+        let mut shift = pos % WORD_LEN;
+        let mut run = 0;
+        while let Some(&word) = self.words.get(wpos) {
+            // Bits differing from `bit` are set.
+            let diff = if bit { !word } else { word } >> shift;
+            if let Some(k) = crate::broadword::lsb(diff) {
+                run += k;
+                break;
+            }
+            run += WORD_LEN - shift;
+            shift = 0;
+            wpos += 1;
+        }
+        Some((bit, run.min(self.len - pos)))
+    }
+
+    /// Returns new data whose `i`-th bit is the `(len - 1 - i)`-th bit of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let data = BitVectorData::from_bits([true, true, false, false, false]);
+    /// let expected = BitVectorData::from_bits([false, false, false, true, true]);
+    /// assert_eq!(data.reversed(), expected);
+    /// ```
+    pub fn reversed(&self) -> BitVectorData {
+        let mut builder = BitVectorBuilder::with_capacity(self.len);
+        let mut rest = self.len;
+        while rest != 0 {
+            let len = rest.min(WORD_LEN);
+            let bits = self.get_bits(rest - len, len).unwrap();
+            builder
+                .push_bits(bits.reverse_bits() >> (WORD_LEN - len), len)
+                .unwrap();
+            rest -= len;
+        }
+        builder.into_data()
+    }
+
+    /// Returns new data rotated to the left by `k` positions,
+    /// i.e., whose `i`-th bit is the `((i + k) % len)`-th bit of `self`,
+    /// in the same manner as [`slice::rotate_left`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let data = BitVectorData::from_bits([true, true, false, false, false]);
+    /// let expected = BitVectorData::from_bits([false, false, false, true, true]);
+    /// assert_eq!(data.rotated(2), expected);
+    /// assert_eq!(data.rotated(7), expected);
+    /// ```
+    pub fn rotated(&self, k: usize) -> BitVectorData {
+        if self.len == 0 {
+            return BitVectorData::default();
+        }
+        let k = k % self.len;
+        let mut builder = BitVectorBuilder::with_capacity(self.len);
+        builder.push_data_range(self, k, self.len);
+        builder.push_data_range(self, 0, k);
+        builder.into_data()
+    }
+
+    /// Serializes the data into a [`Bytes`] buffer.
+    pub fn to_bytes(&self) -> (usize, Bytes) {
+        (self.len, self.words.clone().bytes())
+    }
+
+    /// Consumes the data and creates an iterator over its bits.
+    ///
+    /// The backing [`Bytes`] are moved into the iterator,
+    /// so the source does not have to be kept alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::BitVectorData;
+    ///
+    /// let data = BitVectorData::from_bits([true, false, true]);
+    /// let bits = data.into_bits();
+    /// assert_eq!(bits.len(), 3);
+    /// assert_eq!(bits.collect::<Vec<_>>(), vec![true, false, true]);
+    /// ```
+    pub fn into_bits(self) -> IntoBits {
+        IntoBits { data: self, pos: 0 }
+    }
+}
+
+/// Consuming iterator over bits in a [`BitVectorData`],
+/// created by [`BitVectorData::into_bits`].
+pub struct IntoBits {
+    data: BitVectorData,
+    pos: usize,
+}
+
+impl Iterator for IntoBits {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bit = self.data.access(self.pos)?;
+        self.pos += 1;
+        Some(bit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.data.len() - self.pos;
+        (rest, Some(rest))
+    }
+}
+
+impl ExactSizeIterator for IntoBits {}
+
+/// Iterator over positions of set bits in a [`BitVectorData`],
+/// created by [`BitVectorData::ones`] or [`BitVectorData::ones_in_range`].
+pub struct Ones<'a> {
+    words: &'a [usize],
+    wpos: usize,
+    cur: usize,
+    hi: usize,
+}
+
+impl Iterator for Ones<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cur == 0 {
+            self.wpos += 1;
+            if self.hi <= self.wpos * WORD_LEN {
+                return None;
+            }
+            self.cur = self.words[self.wpos];
+        }
+        let pos = self.wpos * WORD_LEN + self.cur.trailing_zeros() as usize;
+        if self.hi <= pos {
+            return None;
+        }
+        self.cur &= self.cur - 1;
+        Some(pos)
+    }
+}
+
+impl From<BitVectorData> for BitVector<NoIndex> {
+    fn from(data: BitVectorData) -> Self {
+        BitVector::new(data, NoIndex)
+    }
+}
+
+impl Access for BitVectorData {
     fn access(&self, pos: usize) -> Option<bool> {
         if pos < self.len {
             let block = pos / WORD_LEN;
@@ -351,6 +1827,16 @@ pub trait BitVectorIndex: Sized {
     /// Rank query for ones.
     fn rank1(&self, data: &BitVectorData, pos: usize) -> Option<usize>;
 
+    /// Returns the rank query for ones at `pos` together with the `pos`-th bit,
+    /// or [`None`] if `data.len() <= pos`.
+    ///
+    /// The default calls [`Self::rank1`] and [`Access::access`] separately;
+    /// indexes can override it to read the word only once.
+    fn rank1_and_access(&self, data: &BitVectorData, pos: usize) -> Option<(usize, bool)> {
+        let bit = data.access(pos)?;
+        Some((self.rank1(data, pos)?, bit))
+    }
+
     /// Rank query for zeros.
     fn rank0(&self, data: &BitVectorData, pos: usize) -> Option<usize> {
         Some(pos - self.rank1(data, pos)?)
@@ -361,9 +1847,17 @@ pub trait BitVectorIndex: Sized {
 
     /// Select query for zeros.
     fn select0(&self, data: &BitVectorData, k: usize) -> Option<usize>;
+
+    /// Returns the number of bytes occupied by the auxiliary index data.
+    ///
+    /// Defaults to zero for indexes that keep no auxiliary data.
+    fn size_in_bytes(&self) -> usize {
+        0
+    }
 }
 
-/// Placeholder index that performs linear scans over the data.
+/// Placeholder index that performs linear scans over the data
+/// through [`BitVectorData::as_data_ref`].
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct NoIndex;
 
@@ -373,67 +1867,19 @@ impl BitVectorIndex for NoIndex {
     }
 
     fn num_ones(&self, data: &BitVectorData) -> usize {
-        data.words
-            .iter()
-            .map(|&w| crate::broadword::popcount(w))
-            .sum()
+        data.as_data_ref().num_ones()
     }
 
     fn rank1(&self, data: &BitVectorData, pos: usize) -> Option<usize> {
-        if data.len() < pos {
-            return None;
-        }
-        let mut r = 0;
-        let (wpos, left) = (pos / WORD_LEN, pos % WORD_LEN);
-        for &w in &data.words[..wpos] {
-            r += crate::broadword::popcount(w);
-        }
-        if left != 0 {
-            r += crate::broadword::popcount(data.words[wpos] << (WORD_LEN - left));
-        }
-        Some(r)
+        data.as_data_ref().rank1(pos)
     }
 
     fn select1(&self, data: &BitVectorData, k: usize) -> Option<usize> {
-        let mut wpos = 0;
-        let mut cur_rank = 0;
-        while wpos < data.words.len() {
-            let cnt = crate::broadword::popcount(data.words[wpos]);
-            if k < cur_rank + cnt {
-                break;
-            }
-            wpos += 1;
-            cur_rank += cnt;
-        }
-        if wpos == data.words.len() {
-            return None;
-        }
-        let sel = wpos * WORD_LEN
-            + crate::broadword::select_in_word(data.words[wpos], k - cur_rank).unwrap();
-        Some(sel)
+        data.as_data_ref().select1(k)
     }
 
     fn select0(&self, data: &BitVectorData, k: usize) -> Option<usize> {
-        let mut wpos = 0;
-        let mut cur_rank = 0;
-        while wpos < data.words.len() {
-            let cnt = crate::broadword::popcount(!data.words[wpos]);
-            if k < cur_rank + cnt {
-                break;
-            }
-            wpos += 1;
-            cur_rank += cnt;
-        }
-        if wpos == data.words.len() {
-            return None;
-        }
-        let sel = wpos * WORD_LEN
-            + crate::broadword::select_in_word(!data.words[wpos], k - cur_rank).unwrap();
-        if sel < data.len() {
-            Some(sel)
-        } else {
-            None
-        }
+        data.as_data_ref().select0(k)
     }
 }
 
@@ -488,6 +1934,11 @@ impl<I> BitVector<I> {
         self.data.len()
     }
 
+    /// Checks if the vector is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
     /// Returns the `len` bits starting at `pos`, or [`None`] if out of bounds.
     pub fn get_bits(&self, pos: usize, len: usize) -> Option<usize> {
         self.data.get_bits(pos, len)
@@ -502,6 +1953,68 @@ impl<I> BitVector<I> {
     pub fn to_vec(&self) -> Vec<bool> {
         self.iter().collect()
     }
+
+    /// Replaces the index with a new one of type `J` built from the same data,
+    /// which is moved without copying.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::{BitVector, BitVectorData, NoIndex, Rank, Rank9SelIndex};
+    ///
+    /// let bv: BitVector<NoIndex> = BitVectorData::from_bits([true, false, true]).into();
+    /// let bv = bv.with_index::<Rank9SelIndex>();
+    /// assert_eq!(bv.rank1(3), Some(2));
+    /// ```
+    pub fn with_index<J: BitVectorIndex>(self) -> BitVector<J> {
+        let index = J::build(&self.data);
+        BitVector::new(self.data, index)
+    }
+
+    /// Returns the number of ones before `pos` together with the `pos`-th bit,
+    /// or [`None`] if `self.len() <= pos`.
+    ///
+    /// This is the primitive of descents in wavelet structures and DACs,
+    /// which branch on a bit and move to the position given by its rank.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::{BitVector, BitVectorData, Rank9SelIndex};
+    ///
+    /// let bv: BitVector<Rank9SelIndex> =
+    ///     BitVector::from(BitVectorData::from_bits([true, false, true])).with_index();
+    /// assert_eq!(bv.rank1_and_access(2), Some((1, true)));
+    /// assert_eq!(bv.rank1_and_access(3), None);
+    /// ```
+    pub fn rank1_and_access(&self, pos: usize) -> Option<(usize, bool)>
+    where
+        I: BitVectorIndex,
+    {
+        self.index.rank1_and_access(&self.data, pos)
+    }
+
+    /// Returns the underlying bit data without the index.
+    pub const fn as_data(&self) -> &BitVectorData {
+        &self.data
+    }
+
+    /// Returns the raw word slice of the underlying data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::{BitVector, BitVectorBuilder, Rank9SelIndex};
+    ///
+    /// let mut builder = BitVectorBuilder::new();
+    /// builder.extend_bits([true, false, true, true]).unwrap();
+    /// let bv: BitVector<Rank9SelIndex> = builder.freeze::<Rank9SelIndex>();
+    /// assert_eq!(bv.words(), &[0b1101]);
+    /// assert_eq!(bv.as_data().len(), 4);
+    /// ```
+    pub fn words(&self) -> &[usize] {
+        self.data.words()
+    }
 }
 
 impl<I: BitVectorIndex> NumBits for BitVector<I> {
@@ -540,11 +2053,34 @@ impl<I: BitVectorIndex> Select for BitVector<I> {
     }
 }
 
-pub use rank9sel::Rank9SelIndex;
+impl ToDense for BitVectorData {
+    fn to_dense(&self) -> BitVectorData {
+        self.clone()
+    }
+}
+
+impl<I> ToDense for BitVector<I> {
+    fn to_dense(&self) -> BitVectorData {
+        self.data.clone()
+    }
+}
+
+pub use cached_select::CachedSelect;
+pub use chunked_bitmap::ChunkedBitmap;
+pub use data_ref::BitVectorDataRef;
+#[cfg(feature = "std")]
+pub use lazy_index::LazyIndex;
+pub use mutable_rank::MutableRankBitVector;
+pub use nullable_bits::{NullableBits, NullableBitsBuilder};
+pub use rank9sel::{Rank9SelIndex, Rank9SelIndexBuilder};
+pub use sampled_rank::SampledRankIndex;
+pub use slice::BitVectorSlice;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
 
     #[test]
     fn no_index_wrapper() {
@@ -559,10 +2095,133 @@ mod tests {
         assert_eq!(bv.select0(0), Some(1));
     }
 
+    #[test]
+    fn windowed_popcounts_matches_rank() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for len in [0, 1, 63, 64, 65, 300] {
+            let data = BitVectorData::from_bits((0..len).map(|_| rng.gen_bool(0.4)));
+            let bv = BitVector::new(data.clone(), NoIndex).with_index::<Rank9SelIndex>();
+            for w in [0, 1, 2, 63, 64, 65, 300, 301] {
+                let counts = data.windowed_popcounts(w);
+                if len < w {
+                    assert!(counts.is_empty());
+                    continue;
+                }
+                let expected: Vec<usize> = (0..=len - w)
+                    .map(|i| bv.rank1(i + w).unwrap() - bv.rank1(i).unwrap())
+                    .collect();
+                assert_eq!(counts, expected, "len={len}, w={w}");
+            }
+        }
+    }
+
+    #[test]
+    fn for_each_one_matches_select1() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for len in [0, 1, 63, 64, 65, 1000] {
+            let data = BitVectorData::from_bits((0..len).map(|_| rng.gen_bool(0.3)));
+            let mut positions = vec![];
+            data.for_each_one(|p| positions.push(p));
+            let bv = BitVector::new(data, NoIndex).with_index::<Rank9SelIndex>();
+            let expected: Vec<usize> = (0..bv.num_ones()).map(|k| bv.select1(k).unwrap()).collect();
+            assert_eq!(positions, expected);
+        }
+
+        let data = BitVectorData::from_bytes(3, Bytes::from_source(vec![usize::MAX])).unwrap();
+        assert!(data.validate().is_err());
+        let mut positions = vec![];
+        data.for_each_one(|p| positions.push(p));
+        assert_eq!(positions, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn build_rank_select_kinds() {
+        let bits: Vec<bool> = (0..300).map(|i| (i * 7 + i / 3) % 5 < 2).collect();
+        let ones: Vec<usize> = (0..bits.len()).filter(|&i| bits[i]).collect();
+        let zeros: Vec<usize> = (0..bits.len()).filter(|&i| !bits[i]).collect();
+        for kind in [BitVecKind::NoIndex, BitVecKind::Rank9Sel] {
+            let bv = build_rank_select(kind, bits.iter().copied());
+            assert_eq!(bv.num_bits(), bits.len());
+            assert_eq!(bv.num_ones(), ones.len());
+            for (pos, &bit) in bits.iter().enumerate() {
+                assert_eq!(bv.access(pos), Some(bit));
+                assert_eq!(
+                    bv.rank1(pos),
+                    Some(ones.iter().filter(|&&p| p < pos).count())
+                );
+            }
+            for (k, &pos) in ones.iter().enumerate() {
+                assert_eq!(bv.select1(k), Some(pos));
+            }
+            for (k, &pos) in zeros.iter().enumerate() {
+                assert_eq!(bv.select0(k), Some(pos));
+            }
+            assert_eq!(bv.select1(ones.len()), None);
+            assert_eq!(bv.select0(zeros.len()), None);
+        }
+    }
+
+    #[test]
+    fn no_index_select0_partial_last_word() {
+        let mut bits = vec![true; 100];
+        bits[3] = false;
+        bits[97] = false;
+        let bv = BitVector::from(BitVectorData::from_bits(bits));
+        assert_eq!(bv.num_zeros(), 2);
+        assert_eq!(bv.select0(0), Some(3));
+        assert_eq!(bv.select0(1), Some(97));
+        assert_eq!(bv.select0(2), None);
+        assert_eq!(bv.last_zero(), Some(97));
+
+        let data = BitVectorData::from_words(vec![0, 0], 70).unwrap();
+        assert_eq!(NoIndex.select0(&data, 69), Some(69));
+        assert_eq!(NoIndex.select0(&data, 70), None);
+    }
+
+    #[test]
+    fn select_ext_edges() {
+        for len in [1, 64, 65] {
+            let mut builder = BitVectorBuilder::new();
+            builder.extend_bits(vec![false; len]).unwrap();
+            let zeros = builder.freeze::<Rank9SelIndex>();
+            assert_eq!(zeros.first_one(), None);
+            assert_eq!(zeros.last_one(), None);
+            assert_eq!(zeros.first_zero(), Some(0));
+            assert_eq!(zeros.last_zero(), Some(len - 1));
+
+            let ones = BitVector::from(BitVectorData::from_bits(vec![true; len]));
+            assert_eq!(ones.first_one(), Some(0));
+            assert_eq!(ones.last_one(), Some(len - 1));
+            assert_eq!(ones.first_zero(), None);
+            assert_eq!(ones.last_zero(), None);
+        }
+        let empty = BitVector::from(BitVectorData::default());
+        assert_eq!(empty.first_one(), None);
+        assert_eq!(empty.last_zero(), None);
+    }
+
+    #[test]
+    fn no_index_select_matches_naive() {
+        for len in [0, 1, 63, 64, 65, 200] {
+            let bits: Vec<bool> = (0..len).map(|i| (i * 7 + i / 3) % 5 < 2).collect();
+            let bv = BitVector::new(BitVectorData::from_bits(bits.iter().copied()), NoIndex);
+            let ones: Vec<usize> = (0..len).filter(|&i| bits[i]).collect();
+            let zeros: Vec<usize> = (0..len).filter(|&i| !bits[i]).collect();
+            for (k, &pos) in ones.iter().enumerate() {
+                assert_eq!(bv.select1(k), Some(pos));
+            }
+            for (k, &pos) in zeros.iter().enumerate() {
+                assert_eq!(bv.select0(k), Some(pos));
+            }
+            assert_eq!(bv.select1(ones.len()), None);
+            assert_eq!(bv.select0(zeros.len()), None);
+        }
+    }
+
     #[test]
     fn builder_freeze() {
         let mut builder = BitVectorBuilder::new();
-        builder.extend_bits([true, false]);
+        builder.extend_bits([true, false]).unwrap();
         builder.push_bits(0b10, 2).unwrap();
         builder.set_bit(1, true).unwrap();
         let bv: BitVector<NoIndex> = builder.freeze::<NoIndex>();
@@ -573,7 +2232,9 @@ mod tests {
     #[test]
     fn from_bytes_roundtrip() {
         let mut builder = BitVectorBuilder::new();
-        builder.extend_bits([true, false, true, true, false]);
+        builder
+            .extend_bits([true, false, true, true, false])
+            .unwrap();
         let expected: BitVector<NoIndex> = builder.clone().freeze::<NoIndex>();
         let (len, bytes) = builder.into_bytes();
 
@@ -582,6 +2243,99 @@ mod tests {
         assert_eq!(expected, other);
     }
 
+    #[test]
+    fn from_bytes_too_short() {
+        let bytes = Bytes::from_source(vec![0usize]);
+        let e = BitVectorData::from_bytes(65, bytes);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("bytes must hold at least len=65 bits, but got 64 bits.".to_string())
+        );
+    }
+
+    #[test]
+    fn bit_string_roundtrip() {
+        for len in [0, 1, 63, 64, 65, 200] {
+            let data = BitVectorData::from_bits((0..len).map(|i| (i * 7 + i / 3) % 5 < 2));
+            let s = data.to_bit_string();
+            assert_eq!(s.len(), len);
+            assert_eq!(BitVectorData::from_bit_string(&s).unwrap(), data);
+        }
+        let data = BitVectorData::from_bit_string(" 1100_0001\n10 ").unwrap();
+        assert_eq!(data.to_bit_string(), "1100000110");
+    }
+
+    #[test]
+    fn from_bit_string_invalid() {
+        let e = BitVectorData::from_bit_string("10x1");
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("s must consist only of '0', '1', '_', and whitespace, but got 'x'.".to_string())
+        );
+    }
+
+    #[test]
+    fn interleave_equal_lengths() {
+        for len in [0, 1, 31, 32, 33, 100] {
+            let a: Vec<bool> = (0..len).map(|i| i % 3 == 0).collect();
+            let b: Vec<bool> = (0..len).map(|i| i % 5 < 2).collect();
+            let data = BitVectorData::interleave(a.iter().copied(), b.iter().copied()).unwrap();
+            let expected: Vec<bool> = a.iter().zip(&b).flat_map(|(&x, &y)| [x, y]).collect();
+            assert_eq!(data, BitVectorData::from_bits(expected));
+        }
+    }
+
+    #[test]
+    fn interleave_mismatched_lengths() {
+        let e = BitVectorData::interleave([true, false, true], [false]);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("a and b must have the same length, but got 3 and 1.".to_string())
+        );
+        let e = BitVectorData::interleave([], [false, true]);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("a and b must have the same length, but got 0 and 2.".to_string())
+        );
+    }
+
+    #[test]
+    fn from_words_aligned() {
+        let data = BitVectorData::from_words(vec![usize::MAX, 0b10], 128).unwrap();
+        assert_eq!(data.len(), 128);
+        assert_eq!(data.words(), &[usize::MAX, 0b10]);
+        assert_eq!(data.access(65), Some(true));
+        assert_eq!(data.access(128), None);
+        assert_eq!(data, BitVectorData::from_bits(data.clone().into_bits()));
+    }
+
+    #[test]
+    fn from_words_sub_word() {
+        let data = BitVectorData::from_words(vec![usize::MAX, usize::MAX, 1], 70).unwrap();
+        assert_eq!(data.len(), 70);
+        assert_eq!(data.words(), &[usize::MAX, 0b111111]);
+        assert_eq!(NoIndex.num_ones(&data), 70);
+        assert_eq!(data, BitVectorData::from_bits([true; 70]));
+    }
+
+    #[test]
+    fn from_words_too_short() {
+        let e = BitVectorData::from_words(vec![0], 65);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("words must hold at least len=65 bits, but got 64 bits.".to_string())
+        );
+        let e = BitVectorData::from_words(vec![], usize::MAX);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some(format!(
+                "words must hold at least len={} bits, but got 0 bits.",
+                usize::MAX
+            ))
+        );
+        assert!(BitVectorData::from_words(vec![0; 2], usize::MAX - 1).is_err());
+    }
+
     #[test]
     fn get_bits_wrapper() {
         let data = BitVectorData::from_bits([true, false, true, true, false]);
@@ -592,15 +2346,203 @@ mod tests {
         assert_eq!(bv.get_bits(2, 4), None);
     }
 
+    #[test]
+    fn get_bits_overflow() {
+        let data = BitVectorData::from_bits([true, false, true, true, false]);
+        assert_eq!(data.get_bits(usize::MAX, 1), None);
+        assert_eq!(data.get_bits(usize::MAX - 3, WORD_LEN), None);
+        assert_eq!(data.get_bits(usize::MAX - WORD_LEN + 1, WORD_LEN), None);
+    }
+
+    #[test]
+    fn run_length_across_words() {
+        let mut builder = BitVectorBuilder::new();
+        builder
+            .extend_bits(core::iter::repeat(false).take(3))
+            .unwrap();
+        builder
+            .extend_bits(core::iter::repeat(true).take(150))
+            .unwrap();
+        builder
+            .extend_bits(core::iter::repeat(false).take(70))
+            .unwrap();
+        let data = builder.into_data();
+        assert_eq!(data.run_length_from(0), Some((false, 3)));
+        assert_eq!(data.run_length_from(3), Some((true, 150)));
+        assert_eq!(data.run_length_from(64), Some((true, 89)));
+        assert_eq!(data.run_length_from(152), Some((true, 1)));
+        assert_eq!(data.run_length_from(153), Some((false, 70)));
+        assert_eq!(data.run_length_from(222), Some((false, 1)));
+        assert_eq!(data.run_length_from(223), None);
+    }
+
+    #[test]
+    fn run_length_tail() {
+        let data = BitVectorData::from_bits(core::iter::repeat(true).take(128));
+        assert_eq!(data.run_length_from(0), Some((true, 128)));
+        assert_eq!(data.run_length_from(127), Some((true, 1)));
+        assert_eq!(data.run_length_from(128), None);
+    }
+
+    #[test]
+    fn reversed_matches_naive() {
+        for len in [0, 1, 5, 63, 64, 65, 130, 200] {
+            let bits: Vec<bool> = (0..len).map(|i| (i * 7 + i / 3) % 5 < 2).collect();
+            let data = BitVectorData::from_bits(bits.iter().copied());
+            let expected = BitVectorData::from_bits(bits.iter().rev().copied());
+            let reversed = data.reversed();
+            assert_eq!(reversed, expected);
+            assert_eq!(NoIndex.num_ones(&reversed), NoIndex.num_ones(&data));
+        }
+    }
+
+    #[test]
+    fn rotated_matches_naive() {
+        for len in [0, 1, 5, 63, 64, 65, 130, 200] {
+            let bits: Vec<bool> = (0..len).map(|i| (i * 7 + i / 3) % 5 < 2).collect();
+            let data = BitVectorData::from_bits(bits.iter().copied());
+            for k in [0, 1, 3, 63, 64, 100, 250] {
+                let mut rotated = bits.clone();
+                if len != 0 {
+                    rotated.rotate_left(k % len);
+                }
+                let expected = BitVectorData::from_bits(rotated);
+                assert_eq!(data.rotated(k), expected);
+                assert_eq!(NoIndex.num_ones(&data.rotated(k)), NoIndex.num_ones(&data));
+            }
+        }
+    }
+
     #[test]
     fn builder_push_bits_across_word() {
         let mut builder = BitVectorBuilder::new();
-        builder.extend_bits(core::iter::repeat(false).take(62));
+        builder
+            .extend_bits(core::iter::repeat(false).take(62))
+            .unwrap();
         builder.push_bits(0b011111, 6).unwrap();
         let bv: BitVector<NoIndex> = builder.freeze::<NoIndex>();
         assert_eq!(bv.data.get_bits(61, 7).unwrap(), 0b0111110);
     }
 
+    #[test]
+    fn builder_with_capacity_shrinks() {
+        let mut builder = BitVectorBuilder::with_capacity(10_000);
+        builder
+            .extend_bits(core::iter::repeat(true).take(10))
+            .unwrap();
+        let bv: BitVector<NoIndex> = builder.freeze::<NoIndex>();
+        assert_eq!(bv.data.num_words(), 1);
+        assert_eq!(bv.data.to_bytes().1.len(), core::mem::size_of::<usize>());
+        assert_eq!(bv.num_ones(), 10);
+    }
+
+    #[test]
+    fn builder_clear_reuses_capacity() {
+        let mut builder = BitVectorBuilder::with_capacity(1000);
+        let ptr = builder.words.as_ptr();
+        let capa = builder.words.capacity();
+        for round in 0..5 {
+            builder.clear();
+            let bits = (0..1000).map(|i| (i * 7 + round) % 5 < 2);
+            builder.extend_bits(bits.clone()).unwrap();
+            assert_eq!(builder.words.as_ptr(), ptr);
+            assert_eq!(builder.words.capacity(), capa);
+            assert_eq!(builder.clone().into_data(), BitVectorData::from_bits(bits));
+        }
+    }
+
+    #[test]
+    fn with_index_shares_words() {
+        let bv = BitVector::from(BitVectorData::from_bits((0..1000).map(|i| i % 3 == 0)));
+        let ptr = bv.words().as_ptr();
+        let bv = bv.with_index::<Rank9SelIndex>();
+        assert_eq!(bv.words().as_ptr(), ptr);
+        assert_eq!(bv.rank1(1000), Some(334));
+        assert_eq!(bv.select1(1), Some(3));
+        let bv = bv.with_index::<NoIndex>();
+        assert_eq!(bv.words().as_ptr(), ptr);
+        assert_eq!(bv.select1(1), Some(3));
+    }
+
+    #[test]
+    fn builder_freeze_with_stats() {
+        let bits: Vec<bool> = (0..1000).map(|i| (i * 7 + i / 3) % 5 < 2).collect();
+        let mut builder = BitVectorBuilder::new();
+        builder.extend_bits(bits.iter().copied()).unwrap();
+
+        let (bv, stats) = builder.clone().freeze_with_stats::<Rank9SelIndex>();
+        assert_eq!(stats.num_ones, bits.iter().filter(|&&b| b).count());
+        assert_eq!(stats.num_words, bv.words().len());
+        assert_eq!(stats.index_bytes, bv.index.to_bytes().len());
+        assert_ne!(stats.index_bytes, 0);
+
+        let (bv, stats) = builder.freeze_with_stats::<NoIndex>();
+        assert_eq!(stats.num_ones, bv.num_ones());
+        assert_eq!(stats.num_words, 16);
+        assert_eq!(stats.index_bytes, 0);
+
+        let words = [usize::MAX, 0x9E37_79B9_7F4A_7C15];
+        let mut builder = BitVectorBuilder::from_bit(true, 70);
+        builder.push_bits(0b1011_0110, 6).unwrap();
+        builder
+            .set_bits([(3, false), (3, false), (71, true)])
+            .unwrap();
+        builder.extend_from_words(&words, 100).unwrap();
+        builder.push_bits(usize::MAX, 50).unwrap();
+        builder.extend_from_words(&words, 128).unwrap();
+        let mut msb = BitVectorBuilder::new_msb_first();
+        msb.push_bits(0b1011_0110, 7).unwrap();
+        msb.extend_from_words(&words, 90).unwrap();
+        let mut cleared = BitVectorBuilder::from_bit(true, 10);
+        cleared.clear();
+        cleared.push_bit(true).unwrap();
+        for builder in [builder, msb, cleared].iter() {
+            let (bv, stats) = builder.clone().freeze_with_stats::<NoIndex>();
+            assert_eq!(stats.num_ones, bv.num_ones());
+        }
+    }
+
+    #[test]
+    fn builder_freeze_aligned() {
+        let mut builder = BitVectorBuilder::new();
+        builder.extend_bits((0..300).map(|i| i % 3 == 0)).unwrap();
+        let expected: BitVector<NoIndex> = builder.clone().freeze::<NoIndex>();
+        for align in [1, 8, 16, 32, 64, 4096] {
+            let bv = builder.clone().freeze_aligned::<NoIndex>(align).unwrap();
+            assert_eq!(bv.data.words().as_ptr() as usize % align, 0);
+            assert_eq!(bv, expected);
+        }
+        assert!(builder.freeze_aligned::<NoIndex>(24).is_err());
+    }
+
+    #[test]
+    fn builder_max_len() {
+        let mut builder = BitVectorBuilder::with_max_len(70);
+        builder
+            .extend_bits(core::iter::repeat(true).take(60))
+            .unwrap();
+        builder.push_bits(0b101, 3).unwrap();
+        builder.push_bits(0b11, 2).unwrap();
+        builder.push_bit(false).unwrap();
+        builder.push_bits(0b1111, 4).unwrap();
+        assert_eq!(
+            builder.push_bit(true).err().map(|x| x.to_string()),
+            Some("len must be no greater than max_len=70, but got 71.".to_string())
+        );
+        assert!(builder.push_bits(0, 2).is_err());
+        assert!(builder.extend_bits([false]).is_err());
+        let bv: BitVector<NoIndex> = builder.freeze::<NoIndex>();
+        assert_eq!(bv.len(), 70);
+    }
+
+    #[test]
+    fn builder_max_len_extend_keeps_prefix() {
+        let mut builder = BitVectorBuilder::with_max_len(3);
+        assert!(builder.extend_bits([true, true, true, true]).is_err());
+        let bv: BitVector<NoIndex> = builder.freeze::<NoIndex>();
+        assert_eq!(bv.to_vec(), vec![true, true, true]);
+    }
+
     #[test]
     fn builder_from_bit() {
         let builder = BitVectorBuilder::from_bit(true, 5);
@@ -609,6 +2551,533 @@ mod tests {
         assert_eq!(bv.num_ones(), 5);
     }
 
+    #[test]
+    fn into_bits_matches_access() {
+        for len in [0, 1, 63, 64, 65, 200] {
+            let data = BitVectorData::from_bits((0..len).map(|i| (i * 7 + i / 3) % 5 < 2));
+            let expected: Vec<bool> = (0..len).map(|i| data.access(i).unwrap()).collect();
+            let mut bits = data.into_bits();
+            assert_eq!(bits.len(), len);
+            if len != 0 {
+                bits.next();
+                assert_eq!(bits.len(), len - 1);
+            }
+            let mut collected: Vec<bool> = expected.iter().take(1).copied().collect();
+            collected.extend(bits);
+            assert_eq!(collected, expected);
+        }
+    }
+
+    #[test]
+    fn ones_in_range_matches_filter() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for len in [0, 1, 63, 64, 65, 300] {
+            for p in [0.0, 0.1, 0.5, 1.0] {
+                let data = BitVectorData::from_bits((0..len).map(|_| rng.gen_bool(p)));
+                let ones: Vec<usize> = (0..len).filter(|&i| data.access(i).unwrap()).collect();
+                assert_eq!(data.ones().collect::<Vec<_>>(), ones);
+                for _ in 0..50 {
+                    let lo = rng.gen_range(0..=len + 2);
+                    let hi = rng.gen_range(0..=len + 2);
+                    let expected: Vec<usize> = data.ones().filter(|&p| lo <= p && p < hi).collect();
+                    assert_eq!(data.ones_in_range(lo, hi).collect::<Vec<_>>(), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn builder_reserve() {
+        let mut builder = BitVectorBuilder::new();
+        builder.extend_bits((0..70).map(|i| i % 3 == 0)).unwrap();
+        let before = builder.clone().into_data();
+        builder.reserve(1000);
+        assert!(1070 <= builder.capacity_bits());
+        assert_eq!(builder.len(), 70);
+        builder.reserve_exact(5000);
+        assert!(5070 <= builder.capacity_bits());
+        assert_eq!(builder.len(), 70);
+        assert_eq!(builder.clone().into_data(), before);
+        builder.push_bit(true).unwrap();
+        assert_eq!(builder.len(), 71);
+        assert_eq!(builder.into_data().access(70), Some(true));
+    }
+
+    #[test]
+    fn andnot_matches_set_difference() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for len in [0, 1, 63, 64, 65, 300] {
+            let a = BitVectorData::from_bits((0..len).map(|_| rng.gen_bool(0.5)));
+            let b = BitVectorData::from_bits((0..len).map(|_| rng.gen_bool(0.5)));
+            let diff = a.andnot(&b).unwrap();
+            assert_eq!(diff.len(), len);
+            let expected: Vec<usize> = a.ones().filter(|&p| !b.access(p).unwrap()).collect();
+            assert_eq!(diff.ones().collect::<Vec<_>>(), expected);
+            assert_eq!(NoIndex.num_ones(&diff), expected.len());
+
+            let empty = a.andnot(&a).unwrap();
+            assert_eq!(empty.len(), len);
+            assert_eq!(NoIndex.num_ones(&empty), 0);
+        }
+        let e = BitVectorData::from_bits([true]).andnot(&BitVectorData::default());
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("self and other must have the same length, but got 1 and 0.".to_string())
+        );
+    }
+
+    #[test]
+    fn from_bits_sized_pads_and_truncates() {
+        let bits = (0..70).map(|i| i % 3 == 0);
+        let short = BitVectorData::from_bits_sized(130, bits.clone());
+        assert_eq!(short.len(), 130);
+        assert_eq!(
+            short.ones().collect::<Vec<_>>(),
+            (0..70).filter(|i| i % 3 == 0).collect::<Vec<_>>()
+        );
+        assert_eq!(short.num_words(), 3);
+
+        let long = BitVectorData::from_bits_sized(64, bits.clone());
+        assert_eq!(long, BitVectorData::from_bits(bits.take(64)));
+
+        let mut iter = vec![true, false, true].into_iter();
+        let exact = BitVectorData::from_bits_sized(2, iter.by_ref());
+        assert_eq!(exact.to_bit_string(), "10");
+        assert_eq!(iter.next(), Some(true));
+
+        assert!(BitVectorData::from_bits_sized(0, [true]).is_empty());
+    }
+
+    #[test]
+    fn count_words_where_matches_counts() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for len in [0, 1, 63, 64, 65, 300] {
+            let bits: Vec<bool> = (0..len).map(|_| rng.gen_bool(0.5)).collect();
+            let bv = BitVectorData::from_bits(bits.iter().copied());
+            assert_eq!(
+                bv.count_words_where(|w| w.count_ones()),
+                NoIndex.num_ones(&bv)
+            );
+            let pairs = (1..len)
+                .filter(|&i| i % WORD_LEN != 0 && bits[i - 1] && bits[i])
+                .count();
+            assert_eq!(bv.count_words_where(|w| (w & (w >> 1)).count_ones()), pairs);
+            let num_words = (len + WORD_LEN - 1) / WORD_LEN;
+            assert_eq!(
+                bv.count_words_where(|w| w.count_zeros()),
+                num_words * WORD_LEN - NoIndex.num_ones(&bv)
+            );
+        }
+    }
+
+    #[test]
+    fn from_packed_bytes_matches_bits() {
+        use core::convert::TryInto;
+
+        let bits: Vec<bool> = (0..150).map(|i| (i * 7 + i / 3) % 5 < 2).collect();
+        let mut bytes = [0u8; 25];
+        for (i, &b) in bits.iter().enumerate() {
+            bytes[i / 8 + 1] |= u8::from(b) << (i % 8);
+        }
+        let check = |data: &BitVectorData| {
+            assert_eq!(data.len(), 150);
+            for (i, &b) in bits.iter().enumerate() {
+                assert_eq!(data.access(i), Some(b));
+            }
+            assert_eq!(NoIndex.num_ones(data), bits.iter().filter(|&&b| b).count());
+        };
+
+        // Word-aligned bytes are viewed without copying.
+        let words: Vec<usize> = bytes[1..]
+            .chunks(8)
+            .map(|c| usize::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        let aligned = Bytes::from_source(words);
+        let data = BitVectorData::from_packed_bytes(aligned.clone(), 150).unwrap();
+        check(&data);
+        assert_eq!(
+            data.words().as_ptr() as *const u8,
+            aligned.as_ref().as_ptr()
+        );
+
+        // Misaligned or partial-word bytes are repacked.
+        let unaligned = Bytes::from_source(bytes.to_vec());
+        for hi in [25, 20, 22] {
+            let sliced = unaligned
+                .slice_to_bytes(&unaligned.as_ref()[1..hi])
+                .unwrap();
+            check(&BitVectorData::from_packed_bytes(sliced, 150).unwrap());
+        }
+
+        // Set bits beyond len are dropped.
+        let data =
+            BitVectorData::from_packed_bytes(Bytes::from_source(vec![0xFFu8; 16]), 70).unwrap();
+        assert_eq!(NoIndex.num_ones(&data), 70);
+        assert_eq!(data.words(), &[usize::MAX, 0b11_1111]);
+
+        let e = BitVectorData::from_packed_bytes(Bytes::from_source(vec![0u8; 2]), 17);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("bytes must hold at least len=17 bits, but got 16 bits.".to_string())
+        );
+    }
+
+    #[test]
+    fn msb_first_words_roundtrip() {
+        for len in [0, 1, 63, 64, 65, 200] {
+            let bits: Vec<bool> = (0..len).map(|i| (i * 7 + i / 3) % 5 < 2).collect();
+            let lsb = BitVectorData::from_bits(bits.iter().copied());
+
+            let mut msb_words = vec![0usize; (len + WORD_LEN - 1) / WORD_LEN];
+            for (i, &b) in bits.iter().enumerate() {
+                if b {
+                    msb_words[i / WORD_LEN] |= 1 << (WORD_LEN - 1 - i % WORD_LEN);
+                }
+            }
+            let msb = BitVectorData::from_msb_first_words(msb_words.clone(), len).unwrap();
+            assert_eq!(msb, lsb);
+            assert_eq!(lsb.to_msb_first_words(), msb_words);
+            for pos in 0..len.saturating_sub(10) {
+                assert_eq!(msb.get_bits(pos, 10), lsb.get_bits(pos, 10));
+            }
+            if len != 0 {
+                assert_ne!(msb_words, lsb.words());
+            }
+        }
+    }
+
+    #[test]
+    fn builder_extend_from_words() {
+        let words: Vec<usize> = (0..5usize)
+            .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1 << 63)
+            .collect();
+        let src = BitVectorData::from_words(words.clone(), words.len() * WORD_LEN).unwrap();
+        for prefix in [0, 64, 128, 1, 63, 65] {
+            for bit_len in [0, 1, 63, 64, 65, 200, 320] {
+                let mut builder = BitVectorBuilder::new();
+                builder
+                    .extend_bits((0..prefix).map(|i| i % 3 == 0))
+                    .unwrap();
+                builder.extend_from_words(&words, bit_len).unwrap();
+                assert_eq!(builder.len(), prefix + bit_len);
+                let mut expected = BitVectorBuilder::new();
+                expected
+                    .extend_bits((0..prefix).map(|i| i % 3 == 0))
+                    .unwrap();
+                expected
+                    .extend_bits((0..bit_len).map(|i| src.access(i).unwrap()))
+                    .unwrap();
+                assert_eq!(builder.into_data(), expected.into_data());
+            }
+        }
+
+        let mut builder = BitVectorBuilder::new();
+        let e = builder.extend_from_words(&words[..1], 65);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("words must hold at least bit_len=65 bits, but got 64 bits.".to_string())
+        );
+        let mut builder = BitVectorBuilder::with_max_len(100);
+        assert!(builder.extend_from_words(&words, 101).is_err());
+        assert!(builder.is_empty());
+    }
+
+    #[test]
+    fn builder_msb_first() {
+        let bits: Vec<bool> = (0..300).map(|i| (i * 7 + i / 3) % 5 < 2).collect();
+        let lsb = BitVectorData::from_bits(bits.iter().copied());
+        let msb_words = lsb.to_msb_first_words();
+        for prefix in [0, 1, 63, 64, 65] {
+            for bit_len in [0, 1, 63, 64, 65, 200, 300] {
+                let mut builder = BitVectorBuilder::new_msb_first();
+                builder.extend_bits(bits[..prefix].iter().copied()).unwrap();
+                builder.extend_from_words(&msb_words, bit_len).unwrap();
+                let mut expected = BitVectorBuilder::new();
+                expected
+                    .extend_bits(bits[..prefix].iter().copied())
+                    .unwrap();
+                expected
+                    .extend_bits(bits[..bit_len].iter().copied())
+                    .unwrap();
+                assert_eq!(builder.into_data(), expected.into_data());
+            }
+        }
+
+        let mut msb = BitVectorBuilder::new_msb_first();
+        let mut lsb = BitVectorBuilder::new();
+        for (i, len) in [0, 1, 3, 7, 13, 64, 5].iter().copied().enumerate() {
+            let field = 0x9E37_79B9_7F4A_7C15usize.rotate_left(i as u32);
+            msb.push_bits(field, len).unwrap();
+            let reversed = if len == 0 {
+                0
+            } else {
+                field.reverse_bits() >> (WORD_LEN - len)
+            };
+            lsb.push_bits(reversed, len).unwrap();
+        }
+        assert!(msb.push_bits(0, WORD_LEN + 1).is_err());
+        let (msb, lsb) = (msb.into_data(), lsb.into_data());
+        assert_eq!(msb, lsb);
+        for pos in 0..msb.len() {
+            assert_eq!(msb.access(pos), lsb.access(pos));
+        }
+        assert_eq!(
+            BitVectorData::from_msb_first_words(msb.to_msb_first_words(), msb.len()).unwrap(),
+            msb
+        );
+    }
+
+    #[test]
+    fn any_ones_in_ranges() {
+        let data = BitVectorData::from_bits((0..300).map(|i| i == 0 || i == 64 || i == 299));
+        assert!(data.any_ones_in(0, 1));
+        assert!(!data.any_ones_in(1, 64));
+        assert!(data.any_ones_in(1, 65));
+        assert!(data.any_ones_in(64, 65));
+        assert!(!data.any_ones_in(65, 299));
+        assert!(data.any_ones_in(65, 300));
+        assert!(data.any_ones_in(299, 1000));
+        assert!(!data.any_ones_in(64, 64));
+        assert!(!data.any_ones_in(65, 10));
+        assert!(!data.any_ones_in(300, 400));
+
+        let zeros = BitVectorData::from_bits((0..1000).map(|_| false));
+        for (lo, hi) in [(0, 1000), (3, 997), (64, 128)] {
+            assert!(!zeros.any_ones_in(lo, hi));
+        }
+    }
+
+    #[test]
+    fn from_sorted_positions_matches_bits() {
+        use crate::int_vectors::{CompactVector, MonotoneVector};
+
+        for (universe, positions) in [
+            (0, vec![]),
+            (10, vec![]),
+            (1, vec![0]),
+            (200, vec![0, 1, 63, 64, 65, 127, 199]),
+        ] {
+            let expected = BitVectorData::from_bits((0..universe).map(|i| positions.contains(&i)));
+            let cv = CompactVector::from_slice(&positions).unwrap();
+            assert_eq!(
+                BitVectorData::from_sorted_positions(universe, &cv).unwrap(),
+                expected
+            );
+            let mv = MonotoneVector::from_slice(&positions).unwrap();
+            assert_eq!(
+                BitVectorData::from_sorted_positions(universe, &mv).unwrap(),
+                expected
+            );
+        }
+
+        let cv = CompactVector::from_slice(&[1, 5]).unwrap();
+        assert_eq!(
+            BitVectorData::from_sorted_positions(5, &cv)
+                .err()
+                .map(|x| x.to_string()),
+            Some("positions must be less than universe=5, but got 5.".to_string())
+        );
+        let cv = CompactVector::from_slice(&[1, 3, 3]).unwrap();
+        assert_eq!(
+            BitVectorData::from_sorted_positions(5, &cv)
+                .err()
+                .map(|x| x.to_string()),
+            Some("positions must be strictly increasing, but got 3 after 3.".to_string())
+        );
+    }
+
+    #[test]
+    fn from_ranges_matches_positions() {
+        use crate::int_vectors::CompactVector;
+
+        for (universe, ranges) in [
+            (0, vec![]),
+            (0, vec![(0, 0)]),
+            (10, vec![]),
+            (64, vec![(0, 64)]),
+            (200, vec![(0, 1), (1, 63), (63, 63), (64, 65), (70, 199)]),
+            (300, vec![(5, 10), (60, 260), (299, 300)]),
+        ] {
+            let positions: Vec<usize> = ranges.iter().flat_map(|&(a, b)| a..b).collect();
+            let cv = CompactVector::from_slice(&positions).unwrap();
+            let expected = BitVectorData::from_sorted_positions(universe, &cv).unwrap();
+            let data = BitVectorData::from_ranges(universe, ranges).unwrap();
+            assert_eq!(data, expected);
+            assert_eq!(NoIndex.num_ones(&data), positions.len());
+        }
+
+        assert_eq!(
+            BitVectorData::from_ranges(5, [(3, 6)])
+                .err()
+                .map(|x| x.to_string()),
+            Some("ranges must be within 0..universe=5, but got 3..6.".to_string())
+        );
+        assert!(BitVectorData::from_ranges(5, [(3, 2)]).is_err());
+        assert_eq!(
+            BitVectorData::from_ranges(9, [(2, 5), (4, 6)])
+                .err()
+                .map(|x| x.to_string()),
+            Some("ranges must be sorted and disjoint, but got 4..6 after ..5.".to_string())
+        );
+    }
+
+    #[test]
+    fn validate_detects_corruption() {
+        for len in [0, 1, 64, 100] {
+            let data = BitVectorData::from_bits((0..len).map(|i| i % 3 != 1));
+            data.validate().unwrap();
+            let (len, bytes) = data.to_bytes();
+            BitVectorData::from_bytes(len, bytes)
+                .unwrap()
+                .validate()
+                .unwrap();
+        }
+        let data = BitVectorData::from_bits((0..100).map(|i| i % 3 != 1));
+        let (_, bytes) = data.to_bytes();
+        let e = BitVectorData::from_bytes(98, bytes.clone())
+            .unwrap()
+            .validate();
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("bits from len=98 on must be unset, but got a set bit.".to_string())
+        );
+        assert!(BitVectorData::from_bytes(64, bytes)
+            .unwrap()
+            .validate()
+            .is_err());
+        let corrupted = BitVectorData {
+            words: data.words.clone(),
+            len: 129,
+        };
+        let e = corrupted.validate();
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("words must hold at least len=129 bits, but got 128 bits.".to_string())
+        );
+    }
+
+    #[test]
+    fn set_bits_applies_in_order() {
+        let mut builder = BitVectorBuilder::from_bit(false, 100);
+        builder
+            .set_bits((0..100).step_by(7).map(|i| (i, true)))
+            .unwrap();
+        builder
+            .set_bits([(7, false), (7, true), (14, false)])
+            .unwrap();
+        let e = builder.set_bits([(1, true), (100, true), (2, true)]);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("pos must be no greater than self.len()=100, but got 100.".to_string())
+        );
+        let data = builder.into_data();
+        for i in 0..100 {
+            let expected = (i % 7 == 0 && i != 14) || i == 1;
+            assert_eq!(data.access(i), Some(expected), "i={i}");
+        }
+    }
+
+    #[test]
+    fn extend_bits_reserves_size_hint() {
+        let len = 100_000;
+        let mut builder = BitVectorBuilder::new();
+        builder.extend_bits((0..len).map(|i| i % 3 == 0)).unwrap();
+        assert_eq!(
+            builder.capacity_bits(),
+            crate::utils::ceiled_divide(len, WORD_LEN) * WORD_LEN
+        );
+
+        let data = BitVectorData::from_bits((0..len).map(|i| i % 3 == 0));
+        assert_eq!(data.len(), len);
+        assert!((0..len).all(|i| data.access(i) == Some(i % 3 == 0)));
+
+        let mut builder = BitVectorBuilder::with_max_len(10);
+        assert!(builder.extend_bits((0..len).map(|_| true)).is_err());
+        assert_eq!(builder.len(), 10);
+        assert!(builder.capacity_bits() < 1000);
+
+        let mut builder = BitVectorBuilder::with_max_len(10);
+        assert!(builder.extend_bits((0..).map(|i| i % 2 == 0)).is_err());
+        assert_eq!(builder.len(), 10);
+        assert!(builder.capacity_bits() < 1000);
+
+        /// Iterator yielding a few bits but hinting at `usize::MAX` of them.
+        struct HugeHint(usize);
+
+        impl Iterator for HugeHint {
+            type Item = bool;
+
+            fn next(&mut self) -> Option<bool> {
+                self.0 = self.0.checked_sub(1)?;
+                Some(true)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (usize::MAX, None)
+            }
+        }
+
+        let mut builder = BitVectorBuilder::new();
+        builder.push_bit(false).unwrap();
+        builder.extend_bits(HugeHint(3)).unwrap();
+        assert_eq!(builder.into_data().to_bit_string(), "0111");
+    }
+
+    #[test]
+    fn builder_clone_is_independent() {
+        let mut builder = BitVectorBuilder::new();
+        builder.extend_bits([true, false, true]).unwrap();
+        let mut clone = builder.clone();
+        clone.set_bit(0, false).unwrap();
+        clone.push_bit(true).unwrap();
+        builder.push_bit(false).unwrap();
+
+        let frozen = builder.clone().freeze::<NoIndex>();
+        assert_eq!(frozen.data.to_bit_string(), "1010");
+        // Mutating the clones after freezing leaves the frozen data unchanged.
+        builder.set_bit(1, true).unwrap();
+        clone.set_bit(1, true).unwrap();
+        assert_eq!(frozen.data.to_bit_string(), "1010");
+
+        let frozen_clone = clone.clone().freeze::<Rank9SelIndex>();
+        assert_eq!(frozen_clone.data.to_bit_string(), "0111");
+        assert_ne!(frozen.words().as_ptr(), frozen_clone.words().as_ptr());
+        clone.clear();
+        assert_eq!(frozen_clone.data.to_bit_string(), "0111");
+        assert_eq!(frozen_clone.rank1(4), Some(3));
+    }
+
+    #[test]
+    fn rank1_and_access_matches_separate_calls() {
+        for len in [0, 1, 63, 64, 65, 600, 1500] {
+            let data = BitVectorData::from_bits((0..len).map(|i| (i * 7 + i / 3) % 5 < 2));
+            let plain = BitVector::from(data.clone());
+            let indexed = plain.clone().with_index::<Rank9SelIndex>();
+            let sampled = plain.clone().with_index::<SampledRankIndex<2>>();
+            for pos in 0..=len {
+                let expected = if pos < len {
+                    Some((plain.rank1(pos).unwrap(), plain.access(pos).unwrap()))
+                } else {
+                    None
+                };
+                assert_eq!(plain.rank1_and_access(pos), expected);
+                assert_eq!(indexed.rank1_and_access(pos), expected);
+                assert_eq!(sampled.rank1_and_access(pos), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn is_empty() {
+        let empty = BitVectorData::default();
+        assert!(empty.is_empty());
+        assert!(BitVector::new(empty, NoIndex).is_empty());
+
+        let data = BitVectorData::from_bits([false]);
+        assert!(!data.is_empty());
+        assert!(!BitVector::new(data, NoIndex).is_empty());
+    }
+
     #[test]
     fn iter_collects() {
         let data = BitVectorData::from_bits([true, false, true]);
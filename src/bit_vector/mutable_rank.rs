@@ -0,0 +1,356 @@
+//! Mutable bit vector maintaining block sums for rank and select.
+#![cfg(target_pointer_width = "64")]
+
+use anyhow::{anyhow, Result};
+
+use crate::bit_vector::{Access, BitVectorData, NumBits, Rank, Select, ToDense, WORD_LEN};
+use crate::broadword;
+
+/// Number of words in a block.
+const BLOCK_WORDS: usize = 8;
+
+/// Number of bits in a block.
+const BLOCK_BITS: usize = BLOCK_WORDS * WORD_LEN;
+
+/// Bit vector supporting rank and select queries interleaved with updates.
+///
+/// The bits are divided into blocks of 512 bits, whose numbers of ones are kept
+/// in a Fenwick tree. An update of a bit adjusts only the sums covering its block,
+/// instead of rebuilding the index as a frozen [`BitVector`](crate::bit_vector::BitVector)
+/// would require.
+///
+/// Let $`u`$ be the number of bits, so that the tree has $`u / 512`$ leaves.
+///
+///  - An update flips one word and adjusts $`O(\lg u)`$ tree nodes.
+///  - Access reads one word in $`O(1)`$ time.
+///  - Rank sums $`O(\lg u)`$ tree nodes for the blocks before `pos`
+///    and counts the ones in at most eight words of its block, taking $`O(\lg u)`$ time.
+///  - Select descends the tree in $`O(\lg u)`$ steps to the block holding the target
+///    and scans at most eight words of that block, taking $`O(\lg u)`$ time.
+///
+/// Rank and Select are thus logarithmic rather than constant as with
+/// [`Rank9SelIndex`](crate::bit_vector::Rank9SelIndex).
+/// The tree takes one word per block, i.e., $`u / 8`$ bits on top of the bits themselves.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::bit_vector::*;
+///
+/// let mut bv = MutableRankBitVector::from_bits([true, false, false, true]);
+/// assert_eq!(bv.rank1(4), Some(2));
+///
+/// bv.set(1, true)?;
+/// bv.set(3, false)?;
+/// assert_eq!(bv.num_ones(), 2);
+/// assert_eq!(bv.rank1(3), Some(2));
+/// assert_eq!(bv.select1(1), Some(1));
+/// assert_eq!(bv.select0(0), Some(2));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MutableRankBitVector {
+    words: Vec<usize>,
+    len: usize,
+    /// Fenwick tree over the numbers of ones in blocks, indexed from 1.
+    tree: Vec<usize>,
+    num_ones: usize,
+}
+
+impl Default for MutableRankBitVector {
+    fn default() -> Self {
+        Self::from_data(&BitVectorData::default())
+    }
+}
+
+impl MutableRankBitVector {
+    /// Creates a new bit vector from a bit iterator.
+    pub fn from_bits<I: IntoIterator<Item = bool>>(bits: I) -> Self {
+        Self::from_data(&BitVectorData::from_bits(bits))
+    }
+
+    /// Creates a new bit vector holding the bits of `data`.
+    pub fn from_data(data: &BitVectorData) -> Self {
+        let len = data.len();
+        let words = data.words()[..crate::utils::ceiled_divide(len, WORD_LEN)].to_vec();
+        let num_blocks = crate::utils::ceiled_divide(words.len(), BLOCK_WORDS);
+        let mut tree = vec![0; num_blocks + 1];
+        for (b, block) in words.chunks(BLOCK_WORDS).enumerate() {
+            tree[b + 1] += block.iter().map(|w| w.count_ones() as usize).sum::<usize>();
+            let parent = b + 1 + lowbit(b + 1);
+            if parent <= num_blocks {
+                tree[parent] += tree[b + 1];
+            }
+        }
+        let num_ones = words.iter().map(|w| w.count_ones() as usize).sum();
+        Self {
+            words,
+            len,
+            tree,
+            num_ones,
+        }
+    }
+
+    /// Sets the `pos`-th bit to `bit`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `self.len() <= pos`.
+    ///
+    /// # Complexity
+    ///
+    /// $`O(\lg u)`$
+    pub fn set(&mut self, pos: usize, bit: bool) -> Result<()> {
+        if self.len <= pos {
+            return Err(anyhow!(
+                "pos must be less than self.len()={}, but got {pos}.",
+                self.len
+            ));
+        }
+        let (wpos, mask) = (pos / WORD_LEN, 1 << (pos % WORD_LEN));
+        if (self.words[wpos] & mask != 0) == bit {
+            return Ok(());
+        }
+        self.words[wpos] ^= mask;
+        let mut i = pos / BLOCK_BITS + 1;
+        while i < self.tree.len() {
+            if bit {
+                self.tree[i] += 1;
+            } else {
+                self.tree[i] -= 1;
+            }
+            i += lowbit(i);
+        }
+        if bit {
+            self.num_ones += 1;
+        } else {
+            self.num_ones -= 1;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of bits.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the vector is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Copies the bits into immutable [`BitVectorData`],
+    /// e.g., to freeze them with an index once updates are done.
+    pub fn to_data(&self) -> BitVectorData {
+        BitVectorData::from_words(self.words.clone(), self.len).unwrap()
+    }
+
+    /// Gets the number of blocks.
+    #[inline(always)]
+    fn num_blocks(&self) -> usize {
+        self.tree.len() - 1
+    }
+
+    /// Gets the number of ones in the blocks before block `b`.
+    #[inline(always)]
+    fn block_rank1(&self, b: usize) -> usize {
+        let (mut i, mut r) = (b, 0);
+        while i > 0 {
+            r += self.tree[i];
+            i -= lowbit(i);
+        }
+        r
+    }
+
+    /// Gets the number of zeros in the blocks covered by tree node `i`.
+    #[inline(always)]
+    fn node_zeros(&self, i: usize) -> usize {
+        let bits = (i * BLOCK_BITS).min(self.len) - (i - lowbit(i)) * BLOCK_BITS;
+        bits - self.tree[i]
+    }
+
+    /// Finds the block containing the `k`-th one (or zero if `!bit`),
+    /// returning the block and the rank of the target within the block.
+    #[inline(always)]
+    fn find_block(&self, mut k: usize, bit: bool) -> (usize, usize) {
+        let n = self.num_blocks();
+        let mut b = 0;
+        let mut step = if n == 0 {
+            0
+        } else {
+            1 << (63 - n.leading_zeros())
+        };
+        while step != 0 {
+            let i = b + step;
+            if i <= n {
+                let c = if bit {
+                    self.tree[i]
+                } else {
+                    self.node_zeros(i)
+                };
+                if c <= k {
+                    b = i;
+                    k -= c;
+                }
+            }
+            step >>= 1;
+        }
+        (b, k)
+    }
+}
+
+/// Returns the lowest set bit of `i`.
+#[inline(always)]
+const fn lowbit(i: usize) -> usize {
+    i & i.wrapping_neg()
+}
+
+impl NumBits for MutableRankBitVector {
+    fn num_bits(&self) -> usize {
+        self.len
+    }
+
+    fn num_ones(&self) -> usize {
+        self.num_ones
+    }
+}
+
+impl Access for MutableRankBitVector {
+    fn access(&self, pos: usize) -> Option<bool> {
+        if self.len <= pos {
+            return None;
+        }
+        Some((self.words[pos / WORD_LEN] >> (pos % WORD_LEN)) & 1 == 1)
+    }
+}
+
+impl Rank for MutableRankBitVector {
+    fn rank1(&self, pos: usize) -> Option<usize> {
+        if self.len < pos {
+            return None;
+        }
+        let b = pos / BLOCK_BITS;
+        let (wlo, wpos) = (b * BLOCK_WORDS, pos / WORD_LEN);
+        let mut r = self.block_rank1(b);
+        r += self.words[wlo..wpos]
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum::<usize>();
+        if pos % WORD_LEN != 0 {
+            r += (self.words[wpos] & ((1 << (pos % WORD_LEN)) - 1)).count_ones() as usize;
+        }
+        Some(r)
+    }
+
+    fn rank0(&self, pos: usize) -> Option<usize> {
+        Some(pos - self.rank1(pos)?)
+    }
+}
+
+impl Select for MutableRankBitVector {
+    fn select1(&self, k: usize) -> Option<usize> {
+        if self.num_ones <= k {
+            return None;
+        }
+        let (b, mut k) = self.find_block(k, true);
+        for wpos in b * BLOCK_WORDS..self.words.len() {
+            let w = self.words[wpos];
+            let c = w.count_ones() as usize;
+            if k < c {
+                return Some(wpos * WORD_LEN + broadword::select_in_word(w, k)?);
+            }
+            k -= c;
+        }
+        None
+    }
+
+    fn select0(&self, k: usize) -> Option<usize> {
+        if self.len - self.num_ones <= k {
+            return None;
+        }
+        let (b, mut k) = self.find_block(k, false);
+        for wpos in b * BLOCK_WORDS..self.words.len() {
+            let w = !self.words[wpos];
+            let c = w.count_ones() as usize;
+            if k < c {
+                return Some(wpos * WORD_LEN + broadword::select_in_word(w, k)?);
+            }
+            k -= c;
+        }
+        None
+    }
+}
+
+impl ToDense for MutableRankBitVector {
+    fn to_dense(&self) -> BitVectorData {
+        self.to_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    fn check(bv: &MutableRankBitVector, bits: &[bool]) {
+        let ones: Vec<usize> = (0..bits.len()).filter(|&i| bits[i]).collect();
+        let zeros: Vec<usize> = (0..bits.len()).filter(|&i| !bits[i]).collect();
+        assert_eq!(bv.num_bits(), bits.len());
+        assert_eq!(bv.num_ones(), ones.len());
+        let mut r = 0;
+        for pos in 0..=bits.len() {
+            assert_eq!(bv.rank1(pos), Some(r), "pos={pos}");
+            assert_eq!(bv.access(pos), bits.get(pos).copied());
+            r += bits.get(pos).map_or(0, |&b| b as usize);
+        }
+        assert_eq!(bv.rank1(bits.len() + 1), None);
+        for k in 0..=ones.len() {
+            assert_eq!(bv.select1(k), ones.get(k).copied(), "k={k}");
+        }
+        for k in 0..=zeros.len() {
+            assert_eq!(bv.select0(k), zeros.get(k).copied(), "k={k}");
+        }
+    }
+
+    #[test]
+    fn test_updates_and_queries() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for len in [0, 1, 63, 64, 511, 512, 513, 3000] {
+            let mut bits: Vec<bool> = (0..len).map(|_| rng.gen_bool(0.3)).collect();
+            let mut bv = MutableRankBitVector::from_bits(bits.iter().copied());
+            check(&bv, &bits);
+            for _ in 0..5 {
+                for _ in 0..len / 10 + 1 {
+                    if len == 0 {
+                        break;
+                    }
+                    let pos = rng.gen_range(0..len);
+                    let bit = rng.gen_bool(0.5);
+                    bv.set(pos, bit).unwrap();
+                    bits[pos] = bit;
+                }
+                check(&bv, &bits);
+            }
+            assert_eq!(bv.to_data(), BitVectorData::from_bits(bits.iter().copied()));
+            assert_eq!(bv, MutableRankBitVector::from_bits(bits));
+        }
+    }
+
+    #[test]
+    fn test_set_oob() {
+        let mut bv = MutableRankBitVector::from_bits([true, false]);
+        let e = bv.set(2, true);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("pos must be less than self.len()=2, but got 2.".to_string())
+        );
+        assert!(MutableRankBitVector::default().is_empty());
+        assert_eq!(
+            MutableRankBitVector::default(),
+            MutableRankBitVector::from_bits([])
+        );
+    }
+}
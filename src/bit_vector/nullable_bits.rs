@@ -0,0 +1,256 @@
+//! Column of optional bits with a validity bitmap.
+
+use anyhow::{anyhow, Result};
+
+use crate::bit_vector::{Access, BitVectorBuilder, BitVectorData, BitVectorIndex, NoIndex};
+
+/// Column of optional bits in the manner of Arrow's boolean arrays.
+///
+/// A validity bitmap marks the positions holding a value, and a value bitmap
+/// holds the bits themselves. The value bit of a null position is always unset,
+/// so two columns with the same contents compare equal.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::bit_vector::NullableBitsBuilder;
+///
+/// let mut builder = NullableBitsBuilder::new();
+/// builder.push(Some(true));
+/// builder.push(None);
+/// builder.push(Some(false));
+/// builder.set_null(0)?;
+/// let col = builder.freeze();
+///
+/// assert_eq!(col.len(), 3);
+/// assert_eq!(col.get(0), Some(None));
+/// assert_eq!(col.get(2), Some(Some(false)));
+/// assert_eq!(col.get(3), None);
+/// assert_eq!(col.num_valid(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NullableBits {
+    validity: BitVectorData,
+    values: BitVectorData,
+}
+
+impl NullableBits {
+    /// Creates a column from its validity and value bitmaps.
+    ///
+    /// Value bits at null positions are cleared.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the bitmaps have different lengths.
+    pub fn new(validity: BitVectorData, values: BitVectorData) -> Result<Self> {
+        if validity.len() != values.len() {
+            return Err(anyhow!(
+                "validity and values must have the same length, but got {} and {}.",
+                validity.len(),
+                values.len()
+            ));
+        }
+        let words = values
+            .words()
+            .iter()
+            .zip(validity.words())
+            .map(|(&x, &v)| x & v)
+            .collect();
+        let values = BitVectorData::from_words(words, validity.len())?;
+        Ok(Self { validity, values })
+    }
+
+    /// Returns the `pos`-th entry, [`Some(None)`](Some) if it is null,
+    /// or [`None`] if out of bounds.
+    pub fn get(&self, pos: usize) -> Option<Option<bool>> {
+        if !self.validity.access(pos)? {
+            return Some(None);
+        }
+        Some(self.values.access(pos))
+    }
+
+    /// Returns the number of entries, including nulls.
+    pub const fn len(&self) -> usize {
+        self.validity.len()
+    }
+
+    /// Checks if the column is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of non-null entries.
+    pub fn num_valid(&self) -> usize {
+        NoIndex.num_ones(&self.validity)
+    }
+
+    /// Returns the number of null entries.
+    pub fn num_nulls(&self) -> usize {
+        self.len() - self.num_valid()
+    }
+
+    /// Returns the validity bitmap, whose bits are set at non-null positions.
+    pub const fn validity(&self) -> &BitVectorData {
+        &self.validity
+    }
+
+    /// Returns the value bitmap, whose bits are unset at null positions.
+    pub const fn values(&self) -> &BitVectorData {
+        &self.values
+    }
+}
+
+/// Builder for [`NullableBits`].
+#[derive(Debug, Clone, Default)]
+pub struct NullableBitsBuilder {
+    validity: BitVectorBuilder,
+    values: BitVectorBuilder,
+}
+
+impl NullableBitsBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a builder of `len` null entries.
+    pub fn from_nulls(len: usize) -> Self {
+        Self {
+            validity: BitVectorBuilder::from_bit(false, len),
+            values: BitVectorBuilder::from_bit(false, len),
+        }
+    }
+
+    /// Returns the number of entries, including nulls.
+    pub fn len(&self) -> usize {
+        self.validity.len()
+    }
+
+    /// Checks if the builder is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Pushes an entry, which is null if `bit` is [`None`].
+    pub fn push(&mut self, bit: Option<bool>) {
+        self.validity.push_bit(bit.is_some()).unwrap();
+        self.values.push_bit(bit.unwrap_or(false)).unwrap();
+    }
+
+    /// Sets the `pos`-th entry to `bit`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `self.len() <= pos`.
+    pub fn set(&mut self, pos: usize, bit: bool) -> Result<()> {
+        self.check_pos(pos)?;
+        self.validity.set_bit(pos, true)?;
+        self.values.set_bit(pos, bit)
+    }
+
+    /// Sets the `pos`-th entry to null.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `self.len() <= pos`.
+    pub fn set_null(&mut self, pos: usize) -> Result<()> {
+        self.check_pos(pos)?;
+        self.validity.set_bit(pos, false)?;
+        self.values.set_bit(pos, false)
+    }
+
+    /// Finalizes the builder into an immutable [`NullableBits`].
+    pub fn freeze(self) -> NullableBits {
+        NullableBits {
+            validity: self.validity.into_data(),
+            values: self.values.into_data(),
+        }
+    }
+
+    fn check_pos(&self, pos: usize) -> Result<()> {
+        if self.len() <= pos {
+            return Err(anyhow!(
+                "pos must be less than self.len()={}, but got {pos}.",
+                self.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl crate::Freeze for NullableBitsBuilder {
+    type Output = NullableBits;
+
+    fn freeze(self) -> Self::Output {
+        NullableBitsBuilder::freeze(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mixed_column() {
+        let entries = [Some(true), None, Some(false), None, Some(true), Some(false)];
+        let mut builder = NullableBitsBuilder::new();
+        for &e in &entries {
+            builder.push(e);
+        }
+        let col = builder.freeze();
+        assert_eq!(col.len(), entries.len());
+        for (i, &e) in entries.iter().enumerate() {
+            assert_eq!(col.get(i), Some(e));
+        }
+        assert_eq!(col.get(entries.len()), None);
+        assert_eq!(col.num_valid(), 4);
+        assert_eq!(col.num_nulls(), 2);
+        assert_eq!(col.validity().to_bit_string(), "101011");
+        assert_eq!(col.values().to_bit_string(), "100010");
+    }
+
+    #[test]
+    fn test_set() {
+        let mut builder = NullableBitsBuilder::from_nulls(4);
+        builder.set(1, true).unwrap();
+        builder.set(2, false).unwrap();
+        builder.set(3, true).unwrap();
+        builder.set_null(3).unwrap();
+        assert!(builder.set(4, true).is_err());
+        let e = builder.set_null(4);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("pos must be less than self.len()=4, but got 4.".to_string())
+        );
+        let col = builder.freeze();
+        assert_eq!(
+            (0..4).map(|i| col.get(i).unwrap()).collect::<Vec<_>>(),
+            vec![None, Some(true), Some(false), None]
+        );
+        assert_eq!(col.num_valid(), 2);
+
+        let mut other = NullableBitsBuilder::new();
+        for e in [None, Some(true), Some(false), None] {
+            other.push(e);
+        }
+        assert_eq!(other.freeze(), col);
+    }
+
+    #[test]
+    fn test_new() {
+        let validity = BitVectorData::from_bits([true, false, true]);
+        let values = BitVectorData::from_bits([true, true, false]);
+        let col = NullableBits::new(validity, values).unwrap();
+        assert_eq!(col.get(1), Some(None));
+        assert_eq!(col.values().to_bit_string(), "100");
+        assert!(NullableBits::new(
+            BitVectorData::from_bits([true]),
+            BitVectorData::from_bits([true, false])
+        )
+        .is_err());
+        assert!(NullableBits::default().is_empty());
+    }
+}
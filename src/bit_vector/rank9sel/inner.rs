@@ -5,70 +5,242 @@ use anybytes::{Bytes, View};
 
 use anyhow::Result;
 
-use crate::bit_vector::BitVectorData;
+use crate::bit_vector::{BitVectorData, WORD_LEN};
 use crate::broadword;
 
+/// Largest number of words in a basic block, whose ranks fit in one word of 9-bit fields.
 const BLOCK_LEN: usize = 8;
-const SELECT_ONES_PER_HINT: usize = 64 * BLOCK_LEN * 2;
-const SELECT_ZEROS_PER_HINT: usize = SELECT_ONES_PER_HINT;
+const DEFAULT_SELECT_SAMPLE_BITS: usize = 64 * BLOCK_LEN * 2;
 
 /// The index implementation separated from the bit vector.
+///
+/// The bits are divided into basic blocks of `BASIC` words, eight by default as in Vigna's
+/// Rank9. Each block stores its cumulative rank and the ranks of its words in one word each,
+/// so the rank directory takes $`128 / (64 \cdot \text{BASIC})`$ bits per bit, 25% by default.
+/// Smaller blocks narrow the scans of select queries at the cost of more memory.
+/// `BASIC` must be in `1..=8`; other values fail to compile.
+///
+/// An index serialized by [`Self::to_bytes`] must be loaded with the same parameters.
+/// Its header records `BASIC`, so [`Self::from_bytes`] rejects a mismatching block size.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Rank9SelIndex<const SELECT1: bool = true, const SELECT0: bool = true> {
+pub struct Rank9SelIndex<
+    const SELECT1: bool = true,
+    const SELECT0: bool = true,
+    const BASIC: usize = 8,
+> {
     bytes: Bytes,
     len: usize,
+    select_sample_bits: usize,
     block_rank_pairs: View<[usize]>,
     select1_hints: Option<View<[usize]>>,
     select0_hints: Option<View<[usize]>>,
 }
 
-/// Internal builder for [`Rank9SelIndex`].
-#[derive(Default, Debug, Clone)]
-struct Rank9SelIndexBuilder<const SELECT1: bool, const SELECT0: bool> {
-    len: usize,
+/// Builder of [`Rank9SelIndex`] consuming the words of a bit vector in chunks.
+///
+/// Only the block counts are kept while words are pushed, so the index can be
+/// built over data that is never held in memory at once. The select hints are
+/// derived from the block counts in [`Self::finish`].
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::bit_vector::{BitVectorData, Rank9SelIndex, Rank9SelIndexBuilder};
+///
+/// let data = BitVectorData::from_bits((0..1000).map(|i| i % 3 == 0));
+/// let mut builder = Rank9SelIndexBuilder::<true, true>::new();
+/// for chunk in data.words().chunks(5) {
+///     builder.push_words(chunk);
+/// }
+/// let index = builder.finish(data.len())?;
+/// assert_eq!(index, Rank9SelIndex::new(&data));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rank9SelIndexBuilder<
+    const SELECT1: bool = true,
+    const SELECT0: bool = true,
+    const BASIC: usize = 8,
+> {
+    select_sample_bits: usize,
     block_rank_pairs: Vec<usize>,
-    select1_hints: Option<Vec<usize>>,
-    select0_hints: Option<Vec<usize>>,
+    num_words: usize,
+    next_rank: usize,
+    cur_subrank: usize,
+    subranks: usize,
 }
 
-impl<const SELECT1: bool, const SELECT0: bool> Rank9SelIndexBuilder<SELECT1, SELECT0> {
-    /// Creates a builder from the given bit vector data.
-    pub fn new(data: &BitVectorData) -> Self {
-        Self::build_rank(data)
+impl<const SELECT1: bool, const SELECT0: bool, const BASIC: usize> Default
+    for Rank9SelIndexBuilder<SELECT1, SELECT0, BASIC>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SELECT1: bool, const SELECT0: bool, const BASIC: usize>
+    Rank9SelIndexBuilder<SELECT1, SELECT0, BASIC>
+{
+    /// Fails to compile for a basic block size outside `1..=8` words.
+    const VALID_BASIC: () = assert!(
+        0 < BASIC && BASIC <= BLOCK_LEN,
+        "BASIC must be in 1..=8 words."
+    );
+
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID_BASIC;
+        Self {
+            select_sample_bits: DEFAULT_SELECT_SAMPLE_BITS,
+            block_rank_pairs: vec![0],
+            num_words: 0,
+            next_rank: 0,
+            cur_subrank: 0,
+            subranks: 0,
+        }
     }
 
-    /// Builds an index for faster `select1` queries.
-    pub fn select1_hints(self) -> Self {
-        self.build_select1()
+    /// Gets the number of words pushed so far.
+    pub const fn num_words(&self) -> usize {
+        self.num_words
     }
 
-    /// Builds an index for faster `select0` queries.
-    pub fn select0_hints(self) -> Self {
-        self.build_select0()
+    /// Appends the next `words` of the bit vector, updating the block counts.
+    pub fn push_words(&mut self, words: &[usize]) {
+        for &word in words {
+            let word_pop = broadword::popcount(word);
+
+            let shift = self.num_words % BASIC;
+            if shift != 0 {
+                self.subranks <<= 9;
+                self.subranks |= self.cur_subrank;
+            }
+
+            self.next_rank += word_pop;
+            self.cur_subrank += word_pop;
+
+            if shift == BASIC - 1 {
+                // Fields beyond the block repeat its total, as if zero words followed.
+                for _ in BASIC..BLOCK_LEN {
+                    self.subranks <<= 9;
+                    self.subranks |= self.cur_subrank;
+                }
+                self.block_rank_pairs.push(self.subranks);
+                self.block_rank_pairs.push(self.next_rank);
+                self.subranks = 0;
+                self.cur_subrank = 0;
+            }
+            self.num_words += 1;
+        }
     }
 
-    /// Freezes and returns [`Rank9SelIndex`].
-    pub fn build(self) -> Rank9SelIndex<SELECT1, SELECT0> {
-        let mut store = Vec::new();
-        store.push(self.len);
-        store.push(self.block_rank_pairs.len());
+    /// Freezes the index over the first `len` bits of the pushed words.
+    ///
+    /// Bits beyond `len` in the last word must be unset.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `len` does not need exactly [`Self::num_words()`] words.
+    pub fn finish(self, len: usize) -> Result<Rank9SelIndex<SELECT1, SELECT0, BASIC>> {
+        let num_words = crate::utils::ceiled_divide(len, WORD_LEN);
+        if num_words != self.num_words {
+            return Err(anyhow::anyhow!(
+                "len must fit in exactly num_words={} words, but got {len}.",
+                self.num_words
+            ));
+        }
+        Ok(self.freeze(len))
+    }
+
+    /// Sets the number of ones (or zeros) between consecutive select hints
+    /// placed by [`Self::finish`], which defaults to
+    /// [`Rank9SelIndex::DEFAULT_SELECT_SAMPLE_BITS`].
+    ///
+    /// See [`Rank9SelIndex::with_select_sample_bits`] for the trade-off.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `select_sample_bits` is less than
+    /// [`Rank9SelIndex::MIN_SELECT_SAMPLE_BITS`], the number of bits in one block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::{BitVectorData, Rank9SelIndex, Rank9SelIndexBuilder};
+    ///
+    /// let data = BitVectorData::from_bits((0..10_000).map(|i| i % 3 == 0));
+    /// let mut builder = Rank9SelIndexBuilder::<true, true>::new().select_sample_bits(4096)?;
+    /// builder.push_words(data.words());
+    /// let index = builder.finish(data.len())?;
+    /// assert_eq!(index.select_sample_bits(), 4096);
+    /// assert_eq!(index, Rank9SelIndex::with_select_sample_bits(&data, 4096)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn select_sample_bits(mut self, select_sample_bits: usize) -> Result<Self> {
+        let min = Rank9SelIndex::<SELECT1, SELECT0, BASIC>::MIN_SELECT_SAMPLE_BITS;
+        if select_sample_bits < min {
+            return Err(anyhow::anyhow!(
+                "select_sample_bits must be at least {min}, but got {select_sample_bits}."
+            ));
+        }
+        self.select_sample_bits = select_sample_bits;
+        Ok(self)
+    }
+
+    /// Completes the block counts and builds the select hints enabled by the parameters.
+    fn freeze(mut self, len: usize) -> Rank9SelIndex<SELECT1, SELECT0, BASIC> {
+        let left = BLOCK_LEN - (self.num_words % BASIC);
+        for _ in 0..left {
+            self.subranks <<= 9;
+            self.subranks |= self.cur_subrank;
+        }
+        self.block_rank_pairs.push(self.subranks);
+
+        if self.num_words % BASIC != 0 {
+            self.block_rank_pairs.push(self.next_rank);
+            self.block_rank_pairs.push(0);
+        }
+        self.block_rank_pairs.shrink_to_fit();
+
+        let select1_hints = if SELECT1 {
+            self.build_select1()
+        } else {
+            vec![]
+        };
+        let select0_hints = if SELECT0 {
+            self.build_select0()
+        } else {
+            vec![]
+        };
+
+        let mut store = vec![
+            len,
+            self.select_sample_bits,
+            BASIC,
+            self.block_rank_pairs.len(),
+        ];
         store.extend_from_slice(&self.block_rank_pairs);
 
         if SELECT1 {
-            let hints = self.select1_hints.unwrap_or_default();
-            store.push(hints.len());
-            store.extend_from_slice(&hints);
+            store.push(select1_hints.len());
+            store.extend_from_slice(&select1_hints);
         }
 
         if SELECT0 {
-            let hints = self.select0_hints.unwrap_or_default();
-            store.push(hints.len());
-            store.extend_from_slice(&hints);
+            store.push(select0_hints.len());
+            store.extend_from_slice(&select0_hints);
         }
 
         let bytes = Bytes::from_source(store);
         let mut parser = bytes.clone();
         let _len = *parser.view_prefix::<usize>().unwrap();
+        let _select_sample_bits = *parser.view_prefix::<usize>().unwrap();
+        let _basic = *parser.view_prefix::<usize>().unwrap();
         let brp_len = *parser.view_prefix::<usize>().unwrap();
         let block_rank_pairs = parser.view_prefix_with_elems::<[usize]>(brp_len).unwrap();
         let select1_hints = if SELECT1 {
@@ -84,94 +256,42 @@ impl<const SELECT1: bool, const SELECT0: bool> Rank9SelIndexBuilder<SELECT1, SEL
             None
         };
 
-        Rank9SelIndex::<SELECT1, SELECT0> {
+        Rank9SelIndex::<SELECT1, SELECT0, BASIC> {
             bytes,
-            len: self.len,
+            len,
+            select_sample_bits: self.select_sample_bits,
             block_rank_pairs,
             select1_hints,
             select0_hints,
         }
     }
 
-    fn build_rank(data: &BitVectorData) -> Self {
-        let mut next_rank = 0;
-        let mut cur_subrank = 0;
-        let mut subranks = 0;
-
-        let mut block_rank_pairs = vec![next_rank];
-
-        let words = data.words();
-        for (i, &word) in words.iter().enumerate() {
-            let word_pop = broadword::popcount(word);
-
-            let shift = i % BLOCK_LEN;
-            if shift != 0 {
-                subranks <<= 9;
-                subranks |= cur_subrank;
-            }
-
-            next_rank += word_pop;
-            cur_subrank += word_pop;
-
-            if shift == BLOCK_LEN - 1 {
-                block_rank_pairs.push(subranks);
-                block_rank_pairs.push(next_rank);
-                subranks = 0;
-                cur_subrank = 0;
-            }
-        }
-
-        let left = BLOCK_LEN - (data.num_words() % BLOCK_LEN);
-        for _ in 0..left {
-            subranks <<= 9;
-            subranks |= cur_subrank;
-        }
-        block_rank_pairs.push(subranks);
-
-        if data.num_words() % BLOCK_LEN != 0 {
-            block_rank_pairs.push(next_rank);
-            block_rank_pairs.push(0);
-        }
-        block_rank_pairs.shrink_to_fit();
-
-        Self {
-            len: data.len(),
-            block_rank_pairs,
-            select1_hints: None,
-            select0_hints: None,
-        }
-    }
-
-    fn build_select1(mut self) -> Self {
+    fn build_select1(&self) -> Vec<usize> {
         let mut select1_hints = vec![];
-        let mut cur_ones_threshold = SELECT_ONES_PER_HINT;
+        let mut cur_ones_threshold = self.select_sample_bits;
         for i in 0..self.num_blocks() {
             if self.block_rank(i + 1) > cur_ones_threshold {
                 select1_hints.push(i);
-                cur_ones_threshold += SELECT_ONES_PER_HINT;
+                cur_ones_threshold += self.select_sample_bits;
             }
         }
         select1_hints.push(self.num_blocks());
         select1_hints.shrink_to_fit();
-
-        self.select1_hints = Some(select1_hints);
-        self
+        select1_hints
     }
 
-    fn build_select0(mut self) -> Self {
+    fn build_select0(&self) -> Vec<usize> {
         let mut select0_hints = vec![];
-        let mut cur_zeros_threshold = SELECT_ZEROS_PER_HINT;
+        let mut cur_zeros_threshold = self.select_sample_bits;
         for i in 0..self.num_blocks() {
             if self.block_rank0(i + 1) > cur_zeros_threshold {
                 select0_hints.push(i);
-                cur_zeros_threshold += SELECT_ZEROS_PER_HINT;
+                cur_zeros_threshold += self.select_sample_bits;
             }
         }
         select0_hints.push(self.num_blocks());
         select0_hints.shrink_to_fit();
-
-        self.select0_hints = Some(select0_hints);
-        self
+        select0_hints
     }
 
     #[inline(always)]
@@ -186,21 +306,115 @@ impl<const SELECT1: bool, const SELECT0: bool> Rank9SelIndexBuilder<SELECT1, SEL
 
     #[inline(always)]
     fn block_rank0(&self, block: usize) -> usize {
-        block * BLOCK_LEN * 64 - self.block_rank(block)
+        block * BASIC * 64 - self.block_rank(block)
     }
 }
 
-impl<const SELECT1: bool, const SELECT0: bool> Rank9SelIndex<SELECT1, SELECT0> {
+impl<const SELECT1: bool, const SELECT0: bool, const BASIC: usize>
+    Rank9SelIndex<SELECT1, SELECT0, BASIC>
+{
     /// Creates a new index from the given bit vector data.
     pub fn new(data: &BitVectorData) -> Self {
-        let mut builder = Rank9SelIndexBuilder::<SELECT1, SELECT0>::new(data);
-        if SELECT1 {
-            builder = builder.select1_hints();
-        }
-        if SELECT0 {
-            builder = builder.select0_hints();
+        Self::build_from_words(data.words(), data.len())
+    }
+
+    /// Creates a new index over `len` bits held in borrowed `words`,
+    /// without constructing a [`BitVectorData`].
+    ///
+    /// The result equals the index built from a [`BitVectorData`]
+    /// with the same words and length. Words past the `len` bits are ignored,
+    /// and so are the bits beyond `len` in the last word, which need not be unset.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `words` holds fewer than `len` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::{BitVectorData, Rank9SelIndex};
+    ///
+    /// let words = [0b1011, 0b1];
+    /// let index = Rank9SelIndex::<true, true>::build_from_words(&words, 65);
+    /// assert_eq!(index.num_ones(), 4);
+    ///
+    /// let data = BitVectorData::from_words(words.to_vec(), 65)?;
+    /// assert_eq!(index, Rank9SelIndex::new(&data));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_from_words(words: &[usize], len: usize) -> Self {
+        Self::build_from_words_with(Rank9SelIndexBuilder::new(), words, len)
+    }
+
+    /// Creates a new index from the given bit vector data, placing one select
+    /// hint every `select_sample_bits` ones (and zeros).
+    ///
+    /// Smaller intervals narrow the block search of `select1`/`select0` at the cost
+    /// of more hints; larger intervals save space. [`Self::new`] uses
+    /// [`Self::DEFAULT_SELECT_SAMPLE_BITS`], which costs about 3% of the bit vector for the
+    /// hints of both selects on a half-dense vector.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `select_sample_bits` is less than
+    /// [`Self::MIN_SELECT_SAMPLE_BITS`], the number of bits in one block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::{BitVectorData, Rank9SelIndex};
+    ///
+    /// let data = BitVectorData::from_bits((0..10_000).map(|i| i % 3 == 0));
+    /// let fine = Rank9SelIndex::<true, false>::with_select_sample_bits(&data, 512)?;
+    /// let coarse = Rank9SelIndex::<true, false>::with_select_sample_bits(&data, 4096)?;
+    /// assert_eq!(fine.select1(&data, 1000), Some(3000));
+    /// assert_eq!(coarse.select1(&data, 1000), Some(3000));
+    /// assert!(coarse.to_bytes().len() < fine.to_bytes().len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_select_sample_bits(
+        data: &BitVectorData,
+        select_sample_bits: usize,
+    ) -> Result<Self> {
+        let builder = Rank9SelIndexBuilder::<SELECT1, SELECT0, BASIC>::new()
+            .select_sample_bits(select_sample_bits)?;
+        Ok(Self::build_from_words_with(
+            builder,
+            data.words(),
+            data.len(),
+        ))
+    }
+
+    fn build_from_words_with(
+        mut builder: Rank9SelIndexBuilder<SELECT1, SELECT0, BASIC>,
+        words: &[usize],
+        len: usize,
+    ) -> Self {
+        let num_words = crate::utils::ceiled_divide(len, WORD_LEN);
+        assert!(
+            num_words <= words.len(),
+            "words must hold at least len={len} bits, but got {} bits.",
+            words.len().saturating_mul(WORD_LEN)
+        );
+        let words = &words[..num_words];
+        match words.split_last() {
+            Some((&last, init)) if len % WORD_LEN != 0 => {
+                builder.push_words(init);
+                builder.push_words(&[last & ((1 << (len % WORD_LEN)) - 1)]);
+            }
+            _ => builder.push_words(words),
         }
-        builder.build()
+        builder.freeze(len)
+    }
+
+    /// Gets the number of ones (or zeros) between consecutive select hints.
+    #[inline(always)]
+    pub const fn select_sample_bits(&self) -> usize {
+        self.select_sample_bits
     }
 
     /// Gets the number of bits set.
@@ -215,19 +429,53 @@ impl<const SELECT1: bool, const SELECT0: bool> Rank9SelIndex<SELECT1, SELECT0> {
         self.len - self.num_ones()
     }
 
+    /// Number of bits covered by a block whose cumulative rank is stored.
+    pub const BLOCK_BITS: usize = 64 * BASIC;
+
+    /// Default number of ones (or zeros) between consecutive select hints.
+    pub const DEFAULT_SELECT_SAMPLE_BITS: usize = DEFAULT_SELECT_SAMPLE_BITS;
+
+    /// Smallest accepted number of ones (or zeros) between consecutive select hints,
+    /// the number of bits in a block.
+    pub const MIN_SELECT_SAMPLE_BITS: usize = Self::BLOCK_BITS;
+
+    /// Gets the number of blocks of [`Self::BLOCK_BITS`] bits,
+    /// the last of which may be partially filled.
     #[inline(always)]
-    fn num_blocks(&self) -> usize {
+    pub fn num_blocks(&self) -> usize {
         self.block_rank_pairs.len() / 2 - 1
     }
 
+    /// Gets the number of ones before the `block`-th block,
+    /// i.e., in the first `block * Self::BLOCK_BITS` bits.
+    ///
+    /// `block` can be up to [`Self::num_blocks()`] inclusive,
+    /// in which case the number of all ones is returned.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `self.num_blocks() < block`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jerky::bit_vector::{BitVectorData, Rank9SelIndex};
+    ///
+    /// let data = BitVectorData::from_bits((0..1000).map(|i| i % 3 == 0));
+    /// let idx = Rank9SelIndex::<true, true>::new(&data);
+    ///
+    /// assert_eq!(idx.num_blocks(), 2);
+    /// assert_eq!(idx.block_rank(1), idx.rank1(&data, 512).unwrap());
+    /// assert_eq!(idx.block_rank(2), idx.num_ones());
+    /// ```
     #[inline(always)]
-    fn block_rank(&self, block: usize) -> usize {
+    pub fn block_rank(&self, block: usize) -> usize {
         self.block_rank_pairs[block * 2]
     }
 
     #[inline(always)]
     fn sub_block_rank(&self, sub_bpos: usize) -> usize {
-        let (block, left) = (sub_bpos / BLOCK_LEN, sub_bpos % BLOCK_LEN);
+        let (block, left) = (sub_bpos / BASIC, sub_bpos % BASIC);
         self.block_rank(block) + ((self.sub_block_ranks(block) >> ((7 - left) * 9)) & 0x1FF)
     }
 
@@ -238,7 +486,7 @@ impl<const SELECT1: bool, const SELECT0: bool> Rank9SelIndex<SELECT1, SELECT0> {
 
     #[inline(always)]
     fn block_rank0(&self, block: usize) -> usize {
-        block * BLOCK_LEN * 64 - self.block_rank(block)
+        block * BASIC * 64 - self.block_rank(block)
     }
 
     /// Returns the number of ones from the 0-th bit to the `pos-1`-th bit, or
@@ -355,7 +603,7 @@ impl<const SELECT1: bool, const SELECT0: bool> Rank9SelIndex<SELECT1, SELECT0> {
         let block = {
             let (mut a, mut b) = (0, self.num_blocks());
             if let Some(select1_hints) = self.select1_hints.as_ref() {
-                let chunk = k / SELECT_ONES_PER_HINT;
+                let chunk = k / self.select_sample_bits;
                 if chunk != 0 {
                     a = select1_hints[chunk - 1];
                 }
@@ -374,7 +622,7 @@ impl<const SELECT1: bool, const SELECT0: bool> Rank9SelIndex<SELECT1, SELECT0> {
         };
 
         debug_assert!(block < self.num_blocks());
-        let block_offset = block * BLOCK_LEN;
+        let block_offset = block * BASIC;
         let mut cur_rank = self.block_rank(block);
         debug_assert!(cur_rank <= k);
 
@@ -428,7 +676,7 @@ impl<const SELECT1: bool, const SELECT0: bool> Rank9SelIndex<SELECT1, SELECT0> {
         let block = {
             let (mut a, mut b) = (0, self.num_blocks());
             if let Some(select0_hints) = self.select0_hints.as_ref() {
-                let chunk = k / SELECT_ZEROS_PER_HINT;
+                let chunk = k / self.select_sample_bits;
                 if chunk != 0 {
                     a = select0_hints[chunk - 1];
                 }
@@ -447,7 +695,7 @@ impl<const SELECT1: bool, const SELECT0: bool> Rank9SelIndex<SELECT1, SELECT0> {
         };
 
         debug_assert!(block < self.num_blocks());
-        let block_offset = block * BLOCK_LEN;
+        let block_offset = block * BASIC;
         let mut cur_rank = self.block_rank0(block);
         debug_assert!(cur_rank <= k);
 
@@ -467,13 +715,32 @@ impl<const SELECT1: bool, const SELECT0: bool> Rank9SelIndex<SELECT1, SELECT0> {
     }
 }
 
-impl<const SELECT1: bool, const SELECT0: bool> Rank9SelIndex<SELECT1, SELECT0> {
+impl<const SELECT1: bool, const SELECT0: bool, const BASIC: usize>
+    Rank9SelIndex<SELECT1, SELECT0, BASIC>
+{
     /// Reconstructs the index from zero-copy [`Bytes`].
     pub fn from_bytes(bytes: Bytes) -> Result<Self> {
+        #[allow(clippy::let_unit_value)]
+        let _ = Rank9SelIndexBuilder::<SELECT1, SELECT0, BASIC>::VALID_BASIC;
         let mut parser = bytes.clone();
         let len = *parser
             .view_prefix::<usize>()
             .map_err(|e| anyhow::anyhow!(e))?;
+        let select_sample_bits = *parser
+            .view_prefix::<usize>()
+            .map_err(|e| anyhow::anyhow!(e))?;
+        if select_sample_bits < Self::MIN_SELECT_SAMPLE_BITS {
+            return Err(anyhow::anyhow!(
+                "select_sample_bits must be at least {}, but got {select_sample_bits}.",
+                Self::MIN_SELECT_SAMPLE_BITS
+            ));
+        }
+        let basic = *parser
+            .view_prefix::<usize>()
+            .map_err(|e| anyhow::anyhow!(e))?;
+        if basic != BASIC {
+            return Err(anyhow::anyhow!("basic must be {BASIC}, but got {basic}."));
+        }
         let brp_len = *parser
             .view_prefix::<usize>()
             .map_err(|e| anyhow::anyhow!(e))?;
@@ -510,6 +777,7 @@ impl<const SELECT1: bool, const SELECT0: bool> Rank9SelIndex<SELECT1, SELECT0> {
         Ok(Self {
             bytes,
             len,
+            select_sample_bits,
             block_rank_pairs,
             select1_hints,
             select0_hints,
@@ -522,18 +790,11 @@ impl<const SELECT1: bool, const SELECT0: bool> Rank9SelIndex<SELECT1, SELECT0> {
     }
 }
 
-impl<const SELECT1: bool, const SELECT0: bool> crate::bit_vector::BitVectorIndex
-    for Rank9SelIndex<SELECT1, SELECT0>
+impl<const SELECT1: bool, const SELECT0: bool, const BASIC: usize> crate::bit_vector::BitVectorIndex
+    for Rank9SelIndex<SELECT1, SELECT0, BASIC>
 {
     fn build(data: &BitVectorData) -> Self {
-        let mut builder = Rank9SelIndexBuilder::<SELECT1, SELECT0>::new(data);
-        if SELECT1 {
-            builder = builder.select1_hints();
-        }
-        if SELECT0 {
-            builder = builder.select0_hints();
-        }
-        builder.build()
+        Self::new(data)
     }
 
     fn num_ones(&self, _data: &BitVectorData) -> usize {
@@ -544,6 +805,19 @@ impl<const SELECT1: bool, const SELECT0: bool> crate::bit_vector::BitVectorIndex
         Rank9SelIndex::rank1(self, data, pos)
     }
 
+    fn rank1_and_access(&self, data: &BitVectorData, pos: usize) -> Option<(usize, bool)> {
+        if data.len() <= pos {
+            return None;
+        }
+        let (sub_bpos, sub_left) = (pos / 64, pos % 64);
+        let word = data.words()[sub_bpos];
+        let mut r = self.sub_block_rank(sub_bpos);
+        if sub_left != 0 {
+            r += broadword::popcount(word << (64 - sub_left));
+        }
+        Some((r, (word >> sub_left) & 1 == 1))
+    }
+
     fn select1(&self, data: &BitVectorData, k: usize) -> Option<usize> {
         Rank9SelIndex::select1(self, data, k)
     }
@@ -551,12 +825,30 @@ impl<const SELECT1: bool, const SELECT0: bool> crate::bit_vector::BitVectorIndex
     fn select0(&self, data: &BitVectorData, k: usize) -> Option<usize> {
         Rank9SelIndex::select0(self, data, k)
     }
+
+    fn size_in_bytes(&self) -> usize {
+        self.bytes.len()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_block_rank() {
+        for len in [0, 1, 511, 512, 513, 1024, 3000] {
+            let data = BitVectorData::from_bits((0..len).map(|i| (i * 7 + i / 3) % 5 < 2));
+            let idx = Rank9SelIndex::<true, true>::new(&data);
+            let bits = Rank9SelIndex::<true, true>::BLOCK_BITS;
+            assert_eq!(idx.num_blocks(), (len + bits - 1) / bits);
+            for b in 0..=idx.num_blocks() {
+                let pos = (b * bits).min(len);
+                assert_eq!(idx.block_rank(b), idx.rank1(&data, pos).unwrap());
+            }
+        }
+    }
+
     #[test]
     fn test_zero_copy_from_to_bytes() {
         let data = BitVectorData::from_bits([false, true, true, false, true]);
@@ -573,4 +865,173 @@ mod tests {
         let idx2 = Rank9SelIndex::<true, true>::new(&data);
         assert_eq!(idx1, idx2);
     }
+
+    #[test]
+    fn test_build_from_words() {
+        let buffer: Vec<usize> = (0..100usize)
+            .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+            .collect();
+        for (lo, num_words) in [(0, 0), (3, 1), (10, 8), (20, 17), (50, 50)] {
+            let words = &buffer[lo..lo + num_words];
+            let len = num_words * 64;
+            let data = BitVectorData::from_words(words.to_vec(), len).unwrap();
+            assert_eq!(
+                Rank9SelIndex::<true, true>::build_from_words(words, len),
+                Rank9SelIndex::<true, true>::new(&data)
+            );
+            assert_eq!(
+                Rank9SelIndex::<false, true>::build_from_words(words, len),
+                Rank9SelIndex::<false, true>::new(&data)
+            );
+        }
+        let data = BitVectorData::from_bits((0..100).map(|i| i % 3 == 0));
+        assert_eq!(
+            Rank9SelIndex::<true, true>::build_from_words(data.words(), 100),
+            Rank9SelIndex::<true, true>::new(&data)
+        );
+    }
+
+    #[test]
+    fn test_build_from_words_dirty_padding() {
+        let words = [usize::MAX; 3];
+        for len in [0, 1, 63, 64, 70, 128] {
+            let index = Rank9SelIndex::<true, true>::build_from_words(&words, len);
+            let data = BitVectorData::from_bits((0..len).map(|_| true));
+            assert_eq!(index, Rank9SelIndex::<true, true>::new(&data));
+            assert_eq!(index.num_ones(), len);
+            assert_eq!(index.rank1(&data, len), Some(len));
+            assert_eq!(index.select1(&data, len), None);
+            assert_eq!(index.select0(&data, 0), None);
+        }
+    }
+
+    #[test]
+    fn test_select_sample_bits() {
+        let data = BitVectorData::from_bits((0..20_000).map(|i| (i * 7 + i / 3) % 5 < 2));
+        let default = Rank9SelIndex::<true, true>::new(&data);
+        assert_eq!(
+            default.select_sample_bits(),
+            Rank9SelIndex::<true, true>::DEFAULT_SELECT_SAMPLE_BITS
+        );
+        let fine = Rank9SelIndex::<true, true>::with_select_sample_bits(&data, 512).unwrap();
+        let coarse = Rank9SelIndex::<true, true>::with_select_sample_bits(&data, 8192).unwrap();
+        for k in 0..default.num_ones() {
+            let expected = default.select1(&data, k);
+            assert_eq!(fine.select1(&data, k), expected);
+            assert_eq!(coarse.select1(&data, k), expected);
+        }
+        for k in 0..default.num_zeros() {
+            let expected = default.select0(&data, k);
+            assert_eq!(fine.select0(&data, k), expected);
+            assert_eq!(coarse.select0(&data, k), expected);
+        }
+        assert!(coarse.to_bytes().len() < default.to_bytes().len());
+        assert!(default.to_bytes().len() < fine.to_bytes().len());
+
+        let other = Rank9SelIndex::<true, true>::from_bytes(coarse.to_bytes()).unwrap();
+        assert_eq!(other.select_sample_bits(), 8192);
+        assert_eq!(other, coarse);
+    }
+
+    #[test]
+    fn test_select_sample_bits_too_small() {
+        let data = BitVectorData::from_bits([true, false]);
+        assert!(Rank9SelIndex::<true, true>::with_select_sample_bits(&data, 511).is_err());
+        let e = Rank9SelIndexBuilder::<true, true, 4>::new().select_sample_bits(255);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("select_sample_bits must be at least 256, but got 255.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_builder_chunks() {
+        let buffer: Vec<usize> = (0..100usize)
+            .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+            .collect();
+        for len in [0, 1, 64, 511, 512, 513, 4000, 6400] {
+            let data = BitVectorData::from_words(buffer.clone(), len).unwrap();
+            for chunk_len in [1, 3, 8, 100] {
+                let mut builder = Rank9SelIndexBuilder::<true, true>::new();
+                for chunk in data.words().chunks(chunk_len) {
+                    builder.push_words(chunk);
+                }
+                assert_eq!(builder.num_words(), data.num_words());
+                assert_eq!(
+                    builder.finish(len).unwrap(),
+                    Rank9SelIndex::<true, true>::new(&data)
+                );
+            }
+            let mut builder = Rank9SelIndexBuilder::<false, true>::new();
+            builder.push_words(data.words());
+            assert_eq!(
+                builder.finish(len).unwrap(),
+                Rank9SelIndex::<false, true>::new(&data)
+            );
+        }
+    }
+
+    #[test]
+    fn test_builder_wrong_len() {
+        let mut builder = Rank9SelIndexBuilder::<true, true>::new();
+        builder.push_words(&[1, 2]);
+        assert!(builder.clone().finish(128).is_ok());
+        assert!(builder.clone().finish(65).is_ok());
+        assert!(builder.clone().finish(64).is_err());
+        assert!(builder.clone().finish(usize::MAX).is_err());
+        let e = builder.finish(129);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("len must fit in exactly num_words=2 words, but got 129.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_basic_block_sizes() {
+        for len in [0, 1, 63, 64, 65, 300, 511, 512, 513, 5000] {
+            let data = BitVectorData::from_bits((0..len).map(|i| (i * 7 + i / 3) % 5 < 2));
+            let r8 = Rank9SelIndex::<true, true>::new(&data);
+            let r4 = Rank9SelIndex::<true, true, 4>::new(&data);
+            let r1 = Rank9SelIndex::<true, true, 1>::new(&data);
+            assert_eq!(Rank9SelIndex::<true, true, 4>::BLOCK_BITS, 256);
+            assert_eq!(r4.num_blocks(), (len + 255) / 256);
+            for pos in 0..=len {
+                let expected = r8.rank1(&data, pos);
+                assert_eq!(r4.rank1(&data, pos), expected);
+                assert_eq!(r1.rank1(&data, pos), expected);
+                assert_eq!(r4.rank0(&data, pos), r8.rank0(&data, pos));
+            }
+            for k in 0..=len {
+                let expected = r8.select1(&data, k);
+                assert_eq!(r4.select1(&data, k), expected);
+                assert_eq!(r1.select1(&data, k), expected);
+                let expected = r8.select0(&data, k);
+                assert_eq!(r4.select0(&data, k), expected);
+                assert_eq!(r1.select0(&data, k), expected);
+            }
+            let other = Rank9SelIndex::<true, true, 4>::from_bytes(r4.to_bytes()).unwrap();
+            assert_eq!(other, r4);
+            let e = Rank9SelIndex::<true, true>::from_bytes(r4.to_bytes());
+            assert_eq!(
+                e.err().map(|x| x.to_string()),
+                Some("basic must be 8, but got 4.".to_string())
+            );
+            assert!(Rank9SelIndex::<true, true, 4>::from_bytes(r8.to_bytes()).is_err());
+        }
+
+        let data = BitVectorData::from_bits((0..20_000).map(|i| i % 3 == 0));
+        let r8 = Rank9SelIndex::<true, true>::new(&data);
+        let r4 = Rank9SelIndex::<true, true, 4>::new(&data);
+        let r1 = Rank9SelIndex::<true, true, 1>::new(&data);
+        assert!(r8.to_bytes().len() < r4.to_bytes().len());
+        assert!(r4.to_bytes().len() < r1.to_bytes().len());
+        let fine = Rank9SelIndex::<true, true, 1>::with_select_sample_bits(&data, 64).unwrap();
+        assert_eq!(fine.select1(&data, 5000), Some(15000));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_build_from_words_short() {
+        Rank9SelIndex::<true, true>::build_from_words(&[0], 65);
+    }
 }
@@ -1,4 +1,4 @@
 //! Rank9/Select index implementation.
 pub mod inner;
 
-pub use inner::Rank9SelIndex;
+pub use inner::{Rank9SelIndex, Rank9SelIndexBuilder};
@@ -0,0 +1,197 @@
+//! Rank index sampling cumulative counts at a tunable block size.
+#![cfg(target_pointer_width = "64")]
+
+use anybytes::{Bytes, View};
+
+use crate::bit_vector::{BitVectorData, BitVectorIndex, WORD_LEN};
+use crate::broadword;
+
+/// Index storing the number of ones before every block of `BLOCK` words.
+///
+/// A rank query reads one sample and sums the popcounts of at most `BLOCK` words,
+/// and a select query binary-searches the samples and then scans one block.
+/// The index takes $`64 / \texttt{BLOCK}`$ bits per word of data,
+/// so a larger `BLOCK` saves memory at the cost of longer scans.
+/// For example, `BLOCK = 8` costs 12.5% of the data,
+/// half of the overhead of [`Rank9SelIndex`](crate::bit_vector::Rank9SelIndex).
+///
+/// # Panics
+///
+/// Building the index panics if `BLOCK` is zero.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::bit_vector::*;
+///
+/// let mut builder = BitVectorBuilder::new();
+/// builder.extend_bits([true, false, false, true])?;
+/// let bv = builder.freeze::<SampledRankIndex<4>>();
+///
+/// assert_eq!(bv.rank1(4), Some(2));
+/// assert_eq!(bv.select1(1), Some(3));
+/// assert_eq!(bv.select0(1), Some(2));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampledRankIndex<const BLOCK: usize> {
+    /// `samples[b]` is the number of ones in the first `b * BLOCK` words,
+    /// followed by the total number of ones.
+    samples: View<[usize]>,
+}
+
+impl<const BLOCK: usize> SampledRankIndex<BLOCK> {
+    const BLOCK_BITS: usize = BLOCK * WORD_LEN;
+
+    /// Returns the number of zeros in the first `block` blocks.
+    #[inline(always)]
+    fn block_rank0(&self, data: &BitVectorData, block: usize) -> usize {
+        (block * Self::BLOCK_BITS).min(data.len()) - self.samples[block]
+    }
+
+    /// Returns the `k`-th set bit of `words` starting at word `wpos`, if any.
+    #[inline(always)]
+    fn scan_select(words: &[usize], wpos: usize, mut k: usize) -> Option<usize> {
+        for (i, &w) in words.iter().enumerate() {
+            match broadword::select_in_word_or_count(w, k) {
+                Ok(sel) => return Some((wpos + i) * WORD_LEN + sel),
+                Err(cnt) => k -= cnt,
+            }
+        }
+        None
+    }
+}
+
+impl<const BLOCK: usize> BitVectorIndex for SampledRankIndex<BLOCK> {
+    fn build(data: &BitVectorData) -> Self {
+        assert_ne!(BLOCK, 0, "BLOCK must be positive.");
+        let words = data.words();
+        let mut samples = Vec::with_capacity(words.len() / BLOCK + 2);
+        let mut r = 0;
+        for chunk in words.chunks(BLOCK) {
+            samples.push(r);
+            r += chunk.iter().map(|&w| broadword::popcount(w)).sum::<usize>();
+        }
+        samples.push(r);
+        let samples = Bytes::from_source(samples).view::<[usize]>().unwrap();
+        Self { samples }
+    }
+
+    fn num_ones(&self, _data: &BitVectorData) -> usize {
+        self.samples[self.samples.len() - 1]
+    }
+
+    fn rank1(&self, data: &BitVectorData, pos: usize) -> Option<usize> {
+        if data.len() < pos {
+            return None;
+        }
+        let block = pos / Self::BLOCK_BITS;
+        let (wpos, left) = (pos / WORD_LEN, pos % WORD_LEN);
+        let words = data.words();
+        let mut r = self.samples[block];
+        for &w in &words[block * BLOCK..wpos] {
+            r += broadword::popcount(w);
+        }
+        if left != 0 {
+            r += broadword::popcount(words[wpos] << (WORD_LEN - left));
+        }
+        Some(r)
+    }
+
+    fn select1(&self, data: &BitVectorData, k: usize) -> Option<usize> {
+        if self.num_ones(data) <= k {
+            return None;
+        }
+        // The last block whose preceding ones are no more than k.
+        let block = self.samples.partition_point(|&r| r <= k) - 1;
+        let words = data.words();
+        let end = ((block + 1) * BLOCK).min(words.len());
+        Self::scan_select(
+            &words[block * BLOCK..end],
+            block * BLOCK,
+            k - self.samples[block],
+        )
+    }
+
+    fn select0(&self, data: &BitVectorData, k: usize) -> Option<usize> {
+        if self.num_zeros(data) <= k {
+            return None;
+        }
+        let num_blocks = self.samples.len() - 1;
+        let (mut lo, mut hi) = (0, num_blocks);
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            if self.block_rank0(data, mid) <= k {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let words = data.words();
+        let end = ((lo + 1) * BLOCK).min(words.len());
+        let mut k = k - self.block_rank0(data, lo);
+        for (wpos, &w) in words.iter().enumerate().take(end).skip(lo * BLOCK) {
+            match broadword::select_in_word_or_count(!w, k) {
+                Ok(sel) => return Some(wpos * WORD_LEN + sel),
+                Err(cnt) => k -= cnt,
+            }
+        }
+        None
+    }
+
+    fn size_in_bytes(&self) -> usize {
+        self.samples.len() * core::mem::size_of::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bit_vector::NoIndex;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    fn check<const BLOCK: usize>(data: &BitVectorData) {
+        let idx = SampledRankIndex::<BLOCK>::build(data);
+        assert_eq!(idx.num_ones(data), NoIndex.num_ones(data));
+        for pos in 0..=data.len() + 1 {
+            assert_eq!(idx.rank1(data, pos), NoIndex.rank1(data, pos));
+            assert_eq!(idx.rank0(data, pos), NoIndex.rank0(data, pos));
+        }
+        for k in 0..=data.len() {
+            assert_eq!(idx.select1(data, k), NoIndex.select1(data, k));
+            assert_eq!(idx.select0(data, k), NoIndex.select0(data, k));
+        }
+    }
+
+    #[test]
+    fn test_matches_no_index() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for len in [0, 1, 63, 64, 65, 500, 1000, 2000] {
+            for p in [0.0, 0.1, 0.5, 1.0] {
+                let data = BitVectorData::from_bits((0..len).map(|_| rng.gen_bool(p)));
+                check::<1>(&data);
+                check::<2>(&data);
+                check::<3>(&data);
+                check::<8>(&data);
+                check::<32>(&data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_size_in_bytes() {
+        let data = BitVectorData::from_bits((0..64 * 80).map(|i| i % 3 == 0));
+        let size = core::mem::size_of::<usize>();
+        assert_eq!(
+            SampledRankIndex::<1>::build(&data).size_in_bytes(),
+            81 * size
+        );
+        assert_eq!(
+            SampledRankIndex::<8>::build(&data).size_in_bytes(),
+            11 * size
+        );
+    }
+}
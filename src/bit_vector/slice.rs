@@ -0,0 +1,125 @@
+//! Borrowed views over a range of bit vector data.
+
+use crate::bit_vector::{Access, BitVectorData, Rank, WORD_LEN};
+use crate::broadword;
+
+/// Borrowed view of the bits in `lo..hi` of a [`BitVectorData`],
+/// created by [`BitVectorData::slice`].
+///
+/// Positions are relative to `lo`, and no words are copied.
+/// Rank queries count set bits word by word from the origin of the view,
+/// so they take time linear in the queried length.
+///
+/// # Examples
+///
+/// ```
+/// use jerky::bit_vector::{Access, BitVectorData, Rank};
+///
+/// let data = BitVectorData::from_bits([true, false, true, true, false]);
+/// let view = data.slice(1, 4).unwrap();
+///
+/// assert_eq!(view.len(), 3);
+/// assert_eq!(view.access(0), Some(false));
+/// assert_eq!(view.rank1(3), Some(2));
+/// assert_eq!(view.rank0(3), Some(1));
+/// assert_eq!(view.rank1(4), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitVectorSlice<'a> {
+    data: &'a BitVectorData,
+    lo: usize,
+    hi: usize,
+}
+
+impl<'a> BitVectorSlice<'a> {
+    /// Creates a view of `data` restricted to `lo..hi`,
+    /// or returns [`None`] if `hi < lo` or `data.len() < hi`.
+    pub const fn new(data: &'a BitVectorData, lo: usize, hi: usize) -> Option<Self> {
+        if hi < lo || data.len() < hi {
+            return None;
+        }
+        Some(Self { data, lo, hi })
+    }
+
+    /// Returns the number of bits in the view.
+    pub const fn len(&self) -> usize {
+        self.hi - self.lo
+    }
+
+    /// Checks if the view is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.lo == self.hi
+    }
+
+    /// Returns the position in the underlying data where the view starts.
+    pub const fn offset(&self) -> usize {
+        self.lo
+    }
+}
+
+impl Access for BitVectorSlice<'_> {
+    fn access(&self, pos: usize) -> Option<bool> {
+        if pos < self.len() {
+            self.data.access(self.lo + pos)
+        } else {
+            None
+        }
+    }
+}
+
+impl Rank for BitVectorSlice<'_> {
+    fn rank1(&self, pos: usize) -> Option<usize> {
+        if self.len() < pos {
+            return None;
+        }
+        let end = self.lo + pos;
+        let mut r = 0;
+        let mut cur = self.lo;
+        while cur < end {
+            let n = (end - cur).min(WORD_LEN);
+            r += broadword::popcount(self.data.get_bits(cur, n).unwrap());
+            cur += n;
+        }
+        Some(r)
+    }
+
+    fn rank0(&self, pos: usize) -> Option<usize> {
+        Some(pos - self.rank1(pos)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bit_vector::{BitVectorIndex, NoIndex};
+
+    #[test]
+    fn test_relative_queries() {
+        let bits: Vec<bool> = (0..300).map(|i| (i * 7 + i / 3) % 5 < 2).collect();
+        let data = BitVectorData::from_bits(bits.iter().copied());
+        for (lo, hi) in [(0, 0), (0, 300), (3, 70), (64, 128), (65, 300), (299, 300)] {
+            let view = data.slice(lo, hi).unwrap();
+            assert_eq!(view.len(), hi - lo);
+            assert_eq!(view.offset(), lo);
+            for pos in 0..view.len() {
+                assert_eq!(view.access(pos), data.access(lo + pos));
+            }
+            assert_eq!(view.access(view.len()), None);
+            let base = NoIndex.rank1(&data, lo).unwrap();
+            for pos in 0..=view.len() {
+                let expected = NoIndex.rank1(&data, lo + pos).unwrap() - base;
+                assert_eq!(view.rank1(pos), Some(expected));
+                assert_eq!(view.rank0(pos), Some(pos - expected));
+            }
+            assert_eq!(view.rank1(view.len() + 1), None);
+        }
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let data = BitVectorData::from_bits([true, false, true]);
+        assert!(data.slice(2, 1).is_none());
+        assert!(data.slice(0, 4).is_none());
+        assert!(data.slice(3, 3).unwrap().is_empty());
+    }
+}
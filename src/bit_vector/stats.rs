@@ -0,0 +1,90 @@
+//! Generic statistics over bit containers.
+
+use crate::bit_vector::{NumBits, Rank};
+
+/// Returns the number of set bits in `b`, counted by a rank query at the end.
+///
+/// # Examples
+///
+/// ```
+/// use jerky::bit_vector::{stats, BitVector, BitVectorData, NoIndex};
+///
+/// let bv = BitVector::new(BitVectorData::from_bits([true, false, true]), NoIndex);
+/// assert_eq!(stats::ones_count(&bv), 2);
+/// ```
+pub fn ones_count<B: Rank + NumBits>(b: &B) -> usize {
+    b.rank1(b.num_bits()).unwrap()
+}
+
+/// Returns the fraction of set bits in `b`, or `0.0` if `b` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use jerky::bit_vector::{stats, BitVector, BitVectorData, NoIndex};
+///
+/// let bv = BitVector::new(BitVectorData::from_bits([true, false, true, false]), NoIndex);
+/// assert_eq!(stats::density(&bv), 0.5);
+/// ```
+pub fn density<B: Rank + NumBits>(b: &B) -> f64 {
+    let num_bits = b.num_bits();
+    if num_bits == 0 {
+        0.0
+    } else {
+        ones_count(b) as f64 / num_bits as f64
+    }
+}
+
+/// Checks if the fraction of set bits in `b` is at least `threshold`.
+///
+/// # Examples
+///
+/// ```
+/// use jerky::bit_vector::{stats, BitVector, BitVectorData, NoIndex};
+///
+/// let bv = BitVector::new(BitVectorData::from_bits([true, false, true, false]), NoIndex);
+/// assert!(stats::is_dense(&bv, 0.5));
+/// assert!(!stats::is_dense(&bv, 0.6));
+/// ```
+pub fn is_dense<B: Rank + NumBits>(b: &B, threshold: f64) -> bool {
+    threshold <= density(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bit_vector::{
+        BitVector, BitVectorBuilder, BitVectorIndex, NoIndex, Rank9SelIndex, SampledRankIndex,
+    };
+
+    fn check<I: BitVectorIndex>(bits: &[bool]) {
+        let mut builder = BitVectorBuilder::new();
+        builder.extend_bits(bits.iter().copied()).unwrap();
+        let bv: BitVector<I> = builder.freeze();
+        let ones = bits.iter().filter(|&&b| b).count();
+        assert_eq!(ones_count(&bv), ones);
+        assert_eq!(ones_count(&bv), bv.num_ones());
+        if bits.is_empty() {
+            assert_eq!(density(&bv), 0.0);
+        } else {
+            assert_eq!(density(&bv), ones as f64 / bits.len() as f64);
+        }
+        assert!(is_dense(&bv, 0.0));
+        assert_eq!(is_dense(&bv, 1.0), !bits.is_empty() && ones == bits.len());
+    }
+
+    #[test]
+    fn test_indexes() {
+        for len in [0, 1, 63, 64, 65, 512, 1000] {
+            for bits in [
+                vec![false; len],
+                vec![true; len],
+                (0..len).map(|i| i % 3 == 0).collect::<Vec<_>>(),
+            ] {
+                check::<NoIndex>(&bits);
+                check::<Rank9SelIndex>(&bits);
+                check::<SampledRankIndex<4>>(&bits);
+            }
+        }
+    }
+}
@@ -82,6 +82,36 @@ pub const fn select_in_word(x: usize, k: usize) -> Option<usize> {
     if popcount(x) <= k {
         return None;
     }
+    Some(select_in_word_unchecked(x, k))
+}
+
+/// Searches the position of the `k`-th bit set, returning `Err` with the
+/// number of bits set in `x` if it is no more than `k`.
+///
+/// Multi-word select loops can subtract the returned count from `k`
+/// and move on without a second [`popcount`].
+///
+/// # Examples
+///
+/// ```
+/// use jerky::broadword::select_in_word_or_count;
+///
+/// assert_eq!(select_in_word_or_count(0b1000011, 2), Ok(6));
+/// assert_eq!(select_in_word_or_count(0b1000011, 3), Err(3));
+/// assert_eq!(select_in_word_or_count(0, 0), Err(0));
+/// ```
+#[inline(always)]
+pub const fn select_in_word_or_count(x: usize, k: usize) -> Result<usize, usize> {
+    let cnt = popcount(x);
+    if cnt <= k {
+        return Err(cnt);
+    }
+    Ok(select_in_word_unchecked(x, k))
+}
+
+/// Searches the position of the `k`-th bit set, assuming `k < popcount(x)`.
+#[inline(always)]
+const fn select_in_word_unchecked(x: usize, k: usize) -> usize {
     let byte_sums = ONES_STEP_8.wrapping_mul(byte_counts(x));
     let k_step_8 = k * ONES_STEP_8;
     let geq_k_step_8 = ((k_step_8 | MSBS_STEP_8) - byte_sums) & MSBS_STEP_8;
@@ -96,8 +126,51 @@ pub const fn select_in_word(x: usize, k: usize) -> Option<usize> {
         }
     };
     let byte_rank = k - (((byte_sums << 8) >> place) & 0xFF);
-    let sel = place + SELECT_IN_BYTE[((x >> place) & 0xFF) | (byte_rank << 8)] as usize;
-    Some(sel)
+    place + SELECT_IN_BYTE[((x >> place) & 0xFF) | (byte_rank << 8)] as usize
+}
+
+/// Counts the number of set bits in a 128-bit word,
+/// combining [`popcount`] over its two halves.
+///
+/// # Examples
+///
+/// ```
+/// use jerky::broadword::popcount_u128;
+///
+/// assert_eq!(popcount_u128(0), 0);
+/// assert_eq!(popcount_u128(u128::MAX), 128);
+/// assert_eq!(popcount_u128((0b101 << 64) | 0b11), 4);
+/// ```
+#[inline(always)]
+pub const fn popcount_u128(x: u128) -> usize {
+    popcount(x as usize) + popcount((x >> 64) as usize)
+}
+
+/// Searches the position of the `k`-th bit set in a 128-bit word,
+/// returning [`None`] if the number of bits set in `x` is no more than `k`.
+///
+/// # Examples
+///
+/// ```
+/// use jerky::broadword::select_in_word_u128;
+///
+/// let x = (0b1001 << 64) | 0b10;
+/// assert_eq!(select_in_word_u128(x, 0), Some(1));
+/// assert_eq!(select_in_word_u128(x, 1), Some(64));
+/// assert_eq!(select_in_word_u128(x, 2), Some(67));
+/// assert_eq!(select_in_word_u128(x, 3), None);
+/// ```
+#[inline(always)]
+pub const fn select_in_word_u128(x: u128, k: usize) -> Option<usize> {
+    let lo = x as usize;
+    let lo_count = popcount(lo);
+    if k < lo_count {
+        return select_in_word(lo, k);
+    }
+    match select_in_word((x >> 64) as usize, k - lo_count) {
+        Some(sel) => Some(sel + 64),
+        None => None,
+    }
 }
 
 #[inline(always)]
@@ -247,6 +320,8 @@ const DEBRUIJN64: usize = 0x07EDD5E59A4E28C2;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
 
     #[test]
     fn test_select_in_word() {
@@ -258,4 +333,42 @@ mod tests {
             assert_eq!(select_in_word(x, i), Some(k));
         }
     }
+
+    #[test]
+    fn test_select_in_word_or_count() {
+        let x: usize = 0b0000010011000011000011100100000000010000100010000010001100010011;
+        for k in 0..popcount(x) {
+            assert_eq!(
+                select_in_word_or_count(x, k),
+                Ok(select_in_word(x, k).unwrap())
+            );
+        }
+        assert_eq!(select_in_word_or_count(x, 18), Err(18));
+        assert_eq!(select_in_word_or_count(x, 100), Err(18));
+        assert_eq!(select_in_word_or_count(usize::MAX, 63), Ok(63));
+        assert_eq!(select_in_word_or_count(usize::MAX, 64), Err(64));
+    }
+
+    #[test]
+    fn test_popcount_u128() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for _ in 0..1000 {
+            let x: u128 = rng.gen();
+            let (lo, hi) = (x as usize, (x >> 64) as usize);
+            assert_eq!(popcount_u128(x), popcount(lo) + popcount(hi));
+        }
+    }
+
+    #[test]
+    fn test_select_in_word_u128() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for _ in 0..100 {
+            let x: u128 = rng.gen::<u128>() & rng.gen::<u128>();
+            let expected: Vec<usize> = (0..128).filter(|&i| (x >> i) & 1 == 1).collect();
+            for (k, &pos) in expected.iter().enumerate() {
+                assert_eq!(select_in_word_u128(x, k), Some(pos));
+            }
+            assert_eq!(select_in_word_u128(x, expected.len()), None);
+        }
+    }
 }
@@ -24,6 +24,9 @@
 //!
 //! Since there is only one implementation, we do not provide traits for the queries.
 //!
+//! [`WaveletMatrix2d`] builds on [`WaveletMatrix`] to count points of a two-dimensional
+//! point set within a query rectangle.
+//!
 //! ## Wavelet trees
 //!
 //! [`WaveletMatrix`] is a practical variant of Wavelet trees that are functional character sequences.
@@ -33,5 +36,7 @@
 //! Its complexities are related to those of a [bit vector](crate::bit_vector) used internally.
 //! For simplicity, the above table assumes constant-time and linear-space implementation.
 pub mod wavelet_matrix;
+pub mod wavelet_matrix_2d;
 
 pub use wavelet_matrix::WaveletMatrix;
+pub use wavelet_matrix_2d::WaveletMatrix2d;
@@ -314,6 +314,69 @@ where
         Some((start_pos..end_pos).len())
     }
 
+    /// Returns the number of integers less than `val` in the given `range`,
+    /// or [`None`] if `range` is out of bounds.
+    ///
+    /// # Arguments
+    ///
+    /// - `range`: Position range to be searched.
+    /// - `val`: Exclusive upper bound of integers to be counted.
+    ///
+    /// # Complexity
+    ///
+    /// $`O(\lg \sigma)`$
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::{rank9sel::inner::Rank9SelIndex, BitVector};
+    /// use jerky::char_sequences::WaveletMatrix;
+    /// use jerky::int_vectors::{CompactVector, CompactVectorBuilder};
+    ///
+    /// let mut builder = CompactVectorBuilder::new(8)?;
+    /// builder.extend("banana".chars().map(|c| c as usize))?;
+    /// let wm = WaveletMatrix::<Rank9SelIndex>::new(builder.freeze())?;
+    ///
+    /// assert_eq!(wm.count_less_than(0..6, 'b' as usize), Some(3));
+    /// assert_eq!(wm.count_less_than(0..3, 'n' as usize), Some(2));
+    /// assert_eq!(wm.count_less_than(1..4, 'z' as usize), Some(3));
+    /// assert_eq!(wm.count_less_than(4..7, 'z' as usize), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn count_less_than(&self, range: Range<usize>, val: usize) -> Option<usize> {
+        if range.is_empty() {
+            return Some(0);
+        }
+        if self.len() < range.end {
+            return None;
+        }
+        if self.alph_width() < WORD_LEN && val >> self.alph_width() != 0 {
+            return Some(range.len());
+        }
+
+        let mut count = 0;
+        let mut start_pos = range.start;
+        let mut end_pos = range.end;
+
+        // NOTE(kampersanda): rank should be safe because of the precheck.
+        for (depth, layer) in self.layers.iter().enumerate() {
+            let zero_start_pos = layer.rank0(start_pos).unwrap();
+            let zero_end_pos = layer.rank0(end_pos).unwrap();
+            if Self::get_msb(val, depth, self.alph_width()) {
+                count += zero_end_pos - zero_start_pos;
+                start_pos = layer.num_zeros() + start_pos - zero_start_pos;
+                end_pos = layer.num_zeros() + end_pos - zero_end_pos;
+            } else {
+                start_pos = zero_start_pos;
+                end_pos = zero_end_pos;
+            }
+        }
+        Some(count)
+    }
+
     /// Returns the occurrence position of `k`-th `val`,
     /// or [`None`] if there is no such an occurrence.
     ///
@@ -0,0 +1,182 @@
+//! Two-dimensional point set supporting orthogonal range counting,
+//! built on a [`WaveletMatrix`].
+#![cfg(target_pointer_width = "64")]
+
+use anyhow::{anyhow, Result};
+
+use crate::bit_vector::BitVectorIndex;
+use crate::char_sequences::WaveletMatrix;
+use crate::int_vectors::{CompactVector, CompactVectorBuilder};
+
+/// Two-dimensional point set supporting orthogonal range counting,
+/// built on a [`WaveletMatrix`].
+///
+/// Points are sorted by their x-coordinates, which are kept in a [`CompactVector`],
+/// and the corresponding y-coordinates are stored in a [`WaveletMatrix`] in the same order.
+/// A query rectangle is then translated into a position range on the y-coordinates.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::bit_vector::Rank9SelIndex;
+/// use jerky::char_sequences::WaveletMatrix2d;
+///
+/// let points = [(3, 1), (0, 4), (2, 2), (3, 5), (5, 0)];
+/// let wm = WaveletMatrix2d::<Rank9SelIndex>::new(&points)?;
+///
+/// assert_eq!(wm.len(), 5);
+/// assert_eq!(wm.range_count(0, 4, 1, 5), 3);
+/// assert_eq!(wm.range_count(3, 4, 0, 6), 2);
+/// assert_eq!(wm.range_count(4, 4, 0, 6), 0);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # References
+///
+/// - F. Claude, and G. Navarro, "The Wavelet Matrix," In SPIRE 2012.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaveletMatrix2d<I> {
+    xs: CompactVector,
+    ys: WaveletMatrix<I>,
+}
+
+impl<I> WaveletMatrix2d<I>
+where
+    I: BitVectorIndex,
+{
+    /// Creates a new instance from a slice of `(x, y)` points.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if
+    ///
+    ///  - `points` is empty.
+    pub fn new(points: &[(usize, usize)]) -> Result<Self> {
+        if points.is_empty() {
+            return Err(anyhow!("points must not be empty."));
+        }
+
+        let mut points = points.to_vec();
+        points.sort_unstable();
+
+        let xs = CompactVector::from_slice(&points.iter().map(|&(x, _)| x).collect::<Vec<_>>())?;
+        let max_y = points.iter().map(|&(_, y)| y).max().unwrap();
+        let mut ys =
+            CompactVectorBuilder::with_capacity(points.len(), crate::utils::needed_bits(max_y))?;
+        ys.extend(points.iter().map(|&(_, y)| y))?;
+        let ys = WaveletMatrix::new(ys.freeze())?;
+
+        Ok(Self { xs, ys })
+    }
+
+    /// Returns the number of points in the rectangle `[x0, x1) x [y0, y1)`.
+    ///
+    /// An empty rectangle, i.e., `x1 <= x0` or `y1 <= y0`, contains no points.
+    ///
+    /// # Arguments
+    ///
+    /// - `x0`: Inclusive lower bound of x-coordinates.
+    /// - `x1`: Exclusive upper bound of x-coordinates.
+    /// - `y0`: Inclusive lower bound of y-coordinates.
+    /// - `y1`: Exclusive upper bound of y-coordinates.
+    ///
+    /// # Complexity
+    ///
+    /// $`O(\lg n + \lg \sigma)`$ for $`n`$ points whose y-coordinates are in $`[0, \sigma)`$.
+    pub fn range_count(&self, x0: usize, x1: usize, y0: usize, y1: usize) -> usize {
+        if x1 <= x0 || y1 <= y0 {
+            return 0;
+        }
+        let range = self.lower_bound(x0)..self.lower_bound(x1);
+        // NOTE: The position range is always in bounds.
+        let upper = self.ys.count_less_than(range.clone(), y1).unwrap();
+        let lower = self.ys.count_less_than(range, y0).unwrap();
+        upper - lower
+    }
+
+    /// Returns the number of points stored.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.xs.len()
+    }
+
+    /// Checks if the point set is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the first position whose x-coordinate is no less than `x`.
+    fn lower_bound(&self, x: usize) -> usize {
+        let (mut a, mut b) = (0, self.xs.len());
+        while a < b {
+            let mid = a + (b - a) / 2;
+            if self.xs.get_int(mid).unwrap() < x {
+                a = mid + 1;
+            } else {
+                b = mid;
+            }
+        }
+        a
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    use crate::bit_vector::rank9sel::inner::Rank9SelIndex;
+
+    fn brute_force(points: &[(usize, usize)], x0: usize, x1: usize, y0: usize, y1: usize) -> usize {
+        points
+            .iter()
+            .filter(|&&(x, y)| x0 <= x && x < x1 && y0 <= y && y < y1)
+            .count()
+    }
+
+    #[test]
+    fn test_empty_points() {
+        let e = WaveletMatrix2d::<Rank9SelIndex>::new(&[]);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("points must not be empty.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_range_count_random() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for _ in 0..10 {
+            let points: Vec<(usize, usize)> = (0..100)
+                .map(|_| (rng.gen_range(0..50), rng.gen_range(0..30)))
+                .collect();
+            let wm = WaveletMatrix2d::<Rank9SelIndex>::new(&points).unwrap();
+            assert_eq!(wm.len(), points.len());
+            for _ in 0..100 {
+                let x0 = rng.gen_range(0..60);
+                let x1 = rng.gen_range(0..60);
+                let y0 = rng.gen_range(0..40);
+                let y1 = rng.gen_range(0..40);
+                assert_eq!(
+                    wm.range_count(x0, x1, y0, y1),
+                    brute_force(&points, x0, x1, y0, y1)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_count_empty_rectangle() {
+        let points = [(1, 1), (2, 2), (3, 3)];
+        let wm = WaveletMatrix2d::<Rank9SelIndex>::new(&points).unwrap();
+        assert_eq!(wm.range_count(2, 2, 0, 10), 0);
+        assert_eq!(wm.range_count(0, 10, 3, 3), 0);
+        assert_eq!(wm.range_count(3, 1, 0, 10), 0);
+        assert_eq!(wm.range_count(0, 10, 0, 10), 3);
+    }
+}
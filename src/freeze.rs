@@ -0,0 +1,87 @@
+//! Unified interface for finalizing builders.
+
+use crate::bit_vector::{BitVector, BitVectorIndex, IndexedBitVectorBuilder};
+use crate::int_vectors::{CompactVector, CompactVectorBuilder};
+
+/// Interface for builders finalized into an immutable structure.
+///
+/// Each implementation forwards to the inherent `freeze` method of the builder,
+/// so generic construction code can handle any of them.
+/// [`BitVectorBuilder`](crate::bit_vector::BitVectorBuilder) is generic over
+/// the index built on freezing, so it takes part through
+/// [`BitVectorBuilder::indexed`](crate::bit_vector::BitVectorBuilder::indexed).
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::int_vectors::CompactVectorBuilder;
+/// use jerky::Freeze;
+///
+/// fn finish<B: Freeze>(builder: B) -> B::Output {
+///     builder.freeze()
+/// }
+///
+/// let mut builder = CompactVectorBuilder::new(3)?;
+/// builder.push_int(5)?;
+/// let cv = finish(builder);
+/// assert_eq!(cv.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub trait Freeze {
+    /// Structure created by the builder.
+    type Output;
+
+    /// Finalizes the builder.
+    fn freeze(self) -> Self::Output;
+}
+
+impl<I: BitVectorIndex> Freeze for IndexedBitVectorBuilder<I> {
+    type Output = BitVector<I>;
+
+    fn freeze(self) -> Self::Output {
+        IndexedBitVectorBuilder::freeze(self)
+    }
+}
+
+impl Freeze for CompactVectorBuilder {
+    type Output = CompactVector;
+
+    fn freeze(self) -> Self::Output {
+        CompactVectorBuilder::freeze(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bit_vector::{BitVectorBuilder, NoIndex, Rank, Rank9SelIndex};
+    use crate::int_vectors::Access;
+
+    fn finish<B: Freeze>(builder: B) -> B::Output {
+        builder.freeze()
+    }
+
+    #[test]
+    fn bit_vector_builder() {
+        let mut builder = BitVectorBuilder::new();
+        builder.extend_bits([true, false, true, true]).unwrap();
+        let expected = builder.clone().freeze::<Rank9SelIndex>();
+        let bv = finish(builder.clone().indexed::<Rank9SelIndex>());
+        assert_eq!(bv, expected);
+        assert_eq!(bv.rank1(4), Some(3));
+        let bv = finish(builder.indexed::<NoIndex>());
+        assert_eq!(bv.rank1(4), Some(3));
+    }
+
+    #[test]
+    fn compact_vector_builder() {
+        let mut builder = CompactVectorBuilder::new(4).unwrap();
+        builder.extend([3, 9, 15]).unwrap();
+        let cv = finish(builder);
+        assert_eq!(cv.len(), 3);
+        assert_eq!(cv.access(1), Some(9));
+    }
+}
@@ -5,8 +5,9 @@ use anyhow::{anyhow, Result};
 use num_traits::ToPrimitive;
 
 use crate::bit_vector::BitVectorBuilder;
-use crate::bit_vector::{BitVector, BitVectorData, NoIndex};
+use crate::bit_vector::{BitVector, BitVectorData, NoIndex, NumBits, WORD_LEN};
 use crate::int_vectors::prelude::*;
+use crate::int_vectors::Gaps;
 use crate::utils;
 use anybytes::Bytes;
 
@@ -57,10 +58,22 @@ impl CompactVectorBuilder {
 
     /// Creates a new builder reserving space for at least `capa` integers.
     ///
-    /// Currently the reservation is ignored as the builder grows
-    /// automatically.
-    pub fn with_capacity(_capa: usize, width: usize) -> Result<Self> {
-        Self::new(width)
+    /// Unused capacity is released when the builder is frozen.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `width` is outside `1..=64`
+    /// or `capa` integers of `width` bits exceed `usize::MAX` bits.
+    pub fn with_capacity(capa: usize, width: usize) -> Result<Self> {
+        let mut builder = Self::new(width)?;
+        let capa_bits = capa.checked_mul(width).ok_or_else(|| {
+            anyhow!(
+                "capa must be no greater than {}, but got {capa}.",
+                usize::MAX / width
+            )
+        })?;
+        builder.chunks = BitVectorBuilder::with_capacity(capa_bits);
+        Ok(builder)
     }
 
     /// Pushes integer `val` at the end.
@@ -106,6 +119,43 @@ impl CompactVectorBuilder {
         Ok(())
     }
 
+    /// Returns the `pos`-th integer pushed so far, or [`None`] if out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVectorBuilder;
+    ///
+    /// let mut builder = CompactVectorBuilder::new(5)?;
+    /// builder.extend([7, 20])?;
+    /// assert_eq!(builder.get_int(1), Some(20));
+    /// assert_eq!(builder.get_int(2), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_int(&self, pos: usize) -> Option<usize> {
+        if self.len <= pos {
+            return None;
+        }
+        self.chunks.get_bits(pos * self.width, self.width)
+    }
+
+    /// Gets the number of integers pushed so far.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if no integers have been pushed.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets the number of bits used to store each integer.
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
     /// Appends integers at the end.
     ///
     /// # Errors
@@ -144,6 +194,9 @@ impl CompactVectorBuilder {
     }
 }
 
+/// Largest width for which [`CompactVector::count_distinct`] uses a presence bitmap.
+const COUNT_DISTINCT_BITMAP_MAX_WIDTH: usize = 24;
+
 /// Updatable compact vector in which each integer is represented in a fixed number of bits.
 ///
 /// # Memory usage
@@ -176,11 +229,12 @@ pub struct CompactVector {
 }
 
 impl Default for CompactVector {
+    /// Creates an empty vector of width 1, the smallest width accepted by [`Self::new`].
     fn default() -> Self {
         Self {
             chunks: BitVectorBuilder::new().freeze::<NoIndex>(),
             len: 0,
-            width: 0,
+            width: 1,
         }
     }
 }
@@ -232,7 +286,10 @@ impl CompactVector {
     ///
     /// # Errors
     ///
-    /// An error is returned if `width` is not in `1..=64`.
+    /// An error is returned if
+    ///
+    ///  - `width` is not in `1..=64`, or
+    ///  - `capa` integers of `width` bits exceed `usize::MAX` bits.
     ///
     /// # Examples
     ///
@@ -265,8 +322,9 @@ impl CompactVector {
     ///
     /// An error is returned if
     ///
-    ///  - `width` is not in `1..=64`, or
-    ///  - `val` cannot be represent in `width` bits.
+    ///  - `width` is not in `1..=64`,
+    ///  - `val` cannot be represent in `width` bits, or
+    ///  - `len` integers of `width` bits exceed `usize::MAX` bits.
     ///
     /// # Examples
     ///
@@ -300,6 +358,8 @@ impl CompactVector {
     /// Creates a new vector from a slice of integers `vals`.
     ///
     /// The width of each element automatically fits to the maximum value in `vals`.
+    /// An empty `vals` gives an empty vector of width 1, equal to [`Self::default()`];
+    /// use [`Self::try_from_slice`] to reject it instead.
     ///
     /// # Arguments
     ///
@@ -329,21 +389,88 @@ impl CompactVector {
         if vals.is_empty() {
             return Ok(Self::default());
         }
-        let mut max_int = 0;
-        for x in vals {
-            max_int =
-                max_int.max(x.to_usize().ok_or_else(|| {
-                    anyhow!("vals must consist only of values castable into usize.")
-                })?);
-        }
         let mut builder =
-            CompactVectorBuilder::with_capacity(vals.len(), utils::needed_bits(max_int))?;
+            CompactVectorBuilder::with_capacity(vals.len(), super::minimal_width(vals)?)?;
         for x in vals {
             builder.push_int(x.to_usize().unwrap())?;
         }
         Ok(builder.freeze())
     }
 
+    /// Creates a new vector from a non-empty slice of integers `vals`
+    /// in the same way as [`Self::from_slice`].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `vals` is empty or contains an integer
+    /// that cannot be cast to [`usize`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let cv = CompactVector::try_from_slice(&[7, 2])?;
+    /// assert_eq!(cv.width(), 3);
+    /// assert!(CompactVector::try_from_slice::<usize>(&[]).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_from_slice<T>(vals: &[T]) -> Result<Self>
+    where
+        T: ToPrimitive,
+    {
+        if vals.is_empty() {
+            return Err(anyhow!("vals must not be empty."));
+        }
+        Self::from_slice(vals)
+    }
+
+    /// Creates a new vector from a slice of integers `vals`,
+    /// storing each integer in `width` bits.
+    ///
+    /// Unlike [`Self::from_slice`], the width is kept even if all values fit in fewer bits.
+    ///
+    /// # Arguments
+    ///
+    ///  - `vals`: Slice of integers to be stored.
+    ///  - `width`: Number of bits used to store an integer.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if
+    ///
+    ///  - `width` is not in `1..=64`,
+    ///  - `vals` contains an integer that cannot be cast to [`usize`], or
+    ///  - `vals` contains an integer that cannot be represented in `width` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let cv = CompactVector::from_slice_with_width(&[7, 2], 16)?;
+    /// assert_eq!(cv.width(), 16);
+    /// assert_eq!(cv.get_int(0), Some(7));
+    /// assert!(CompactVector::from_slice_with_width(&[7, 2], 2).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_slice_with_width<T>(vals: &[T], width: usize) -> Result<Self>
+    where
+        T: ToPrimitive,
+    {
+        let mut builder = CompactVectorBuilder::with_capacity(vals.len(), width)?;
+        for x in vals {
+            builder.push_int(x.to_usize().ok_or_else(|| {
+                anyhow!("vals must consist only of values castable into usize.")
+            })?)?;
+        }
+        Ok(builder.freeze())
+    }
+
     /// Returns the `pos`-th integer, or [`None`] if out of bounds.
     ///
     /// # Arguments
@@ -368,7 +495,386 @@ impl CompactVector {
     /// # Ok(())
     /// # }
     pub fn get_int(&self, pos: usize) -> Option<usize> {
-        self.chunks.get_bits(pos * self.width, self.width)
+        if self.len <= pos {
+            return None;
+        }
+        self.chunks
+            .get_bits(pos.checked_mul(self.width)?, self.width)
+    }
+
+    /// Returns the `pos`-th integer, or `default` if out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let cv = CompactVector::from_slice(&[5, 256, 0])?;
+    /// assert_eq!(cv.get_int_or(1, 7), 256);
+    /// assert_eq!(cv.get_int_or(3, 7), 7);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn get_int_or(&self, pos: usize, default: usize) -> usize {
+        self.get_int(pos).unwrap_or(default)
+    }
+
+    /// Returns the first position at which the running sum of the integers exceeds `k`,
+    /// or [`None`] if the sum of all integers is no more than `k`.
+    ///
+    /// Viewing the integers as counts of a histogram, this finds the bin
+    /// holding the `k`-th item, e.g., for weighted sampling.
+    ///
+    /// # Complexity
+    ///
+    /// $`O(n)`$ by a linear scan. For many queries over the same counts,
+    /// storing their prefix sums in a [`MonotoneVector`](super::MonotoneVector)
+    /// answers each query with a rank instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let cv = CompactVector::from_slice(&[2, 0, 3])?;
+    /// assert_eq!(cv.cumulative_select(0), Some(0));
+    /// assert_eq!(cv.cumulative_select(1), Some(0));
+    /// assert_eq!(cv.cumulative_select(2), Some(2));
+    /// assert_eq!(cv.cumulative_select(4), Some(2));
+    /// assert_eq!(cv.cumulative_select(5), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cumulative_select(&self, k: usize) -> Option<usize> {
+        let mut sum = 0usize;
+        for (pos, x) in self.iter().enumerate() {
+            // An overflowing sum exceeds any `k`.
+            match sum.checked_add(x) {
+                Some(s) if s <= k => sum = s,
+                _ => return Some(pos),
+            }
+        }
+        None
+    }
+
+    /// Returns the number of distinct integers stored.
+    ///
+    /// If [`Self::width()`] is at most 24, the integers are marked in a presence
+    /// bitmap of $`2^w`$ bits (at most 2 MiB); otherwise, they are collected
+    /// into a [`HashSet`](std::collections::HashSet), which takes memory
+    /// proportional to the number of distinct integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let cv = CompactVector::from_slice(&[3, 1, 3, 0, 1])?;
+    /// assert_eq!(cv.count_distinct(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn count_distinct(&self) -> usize {
+        if self.width <= COUNT_DISTINCT_BITMAP_MAX_WIDTH {
+            let mut present = BitVectorBuilder::from_bit(false, 1 << self.width);
+            for x in self.iter() {
+                present.set_bit(x, true).unwrap();
+            }
+            present.freeze::<NoIndex>().num_ones()
+        } else {
+            self.iter().collect::<std::collections::HashSet<_>>().len()
+        }
+    }
+
+    /// Creates a copy of the vector stored in the smallest width holding
+    /// its largest integer, as [`Self::from_slice`] would choose.
+    ///
+    /// This reclaims space when the integers became smaller than the width,
+    /// e.g., after filtering. The width never grows, and an empty vector
+    /// becomes [`Self::default()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let cv = CompactVector::from_slice_with_width(&[5, 2, 7], 32)?;
+    /// let packed = cv.repack_minimal();
+    /// assert_eq!(packed.width(), 3);
+    /// assert_eq!(packed.to_vec(), vec![5, 2, 7]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn repack_minimal(&self) -> Self {
+        if self.is_empty() {
+            return Self::default();
+        }
+        let width = utils::needed_bits(self.iter().max().unwrap());
+        let mut builder = CompactVectorBuilder::with_capacity(self.len, width).unwrap();
+        builder.extend(self.iter()).unwrap();
+        builder.freeze()
+    }
+
+    /// Checks if the vectors hold the same integer sequence, regardless of
+    /// their widths.
+    ///
+    /// Unlike `==`, which compares the widths and the stored bits exactly,
+    /// this compares the decoded integers. Vectors of different lengths are
+    /// rejected without decoding, and vectors of the same width are compared
+    /// by the words holding their integers, ignoring any bits past them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let a = CompactVector::from_slice_with_width(&[5, 2, 7], 3)?;
+    /// let b = CompactVector::from_slice_with_width(&[5, 2, 7], 8)?;
+    /// assert!(a.value_eq(&b));
+    /// assert_ne!(a, b);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn value_eq(&self, other: &CompactVector) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+        if self.width == other.width {
+            let num_bits = self.len * self.width;
+            let (full, rest) = (num_bits / WORD_LEN, num_bits % WORD_LEN);
+            let (x, y) = (self.chunks.data.words(), other.chunks.data.words());
+            return x[..full] == y[..full]
+                && (rest == 0 || (x[full] ^ y[full]) & ((1 << rest) - 1) == 0);
+        }
+        self.iter().eq(other.iter())
+    }
+
+    /// Gathers the integers at `positions` into `out`,
+    /// writing [`None`] for each position out of bounds.
+    ///
+    /// Words are read directly with the width and mask computed once,
+    /// which is cheaper than calling [`Self::get_int`] per position.
+    ///
+    /// # Arguments
+    ///
+    ///  - `positions`: Positions to read.
+    ///  - `out`: Destination whose `i`-th slot receives the integer at `positions[i]`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `out.len() != positions.len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let cv = CompactVector::from_slice(&[5, 256, 0])?;
+    /// let mut out = [None; 3];
+    /// cv.access_many(&[2, 5, 1], &mut out)?;
+    /// assert_eq!(out, [Some(0), None, Some(256)]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn access_many(&self, positions: &[usize], out: &mut [Option<usize>]) -> Result<()> {
+        if out.len() != positions.len() {
+            return Err(anyhow!(
+                "out must have the same length as positions, but got {} and {}.",
+                out.len(),
+                positions.len()
+            ));
+        }
+        let words = self.chunks.words();
+        let width = self.width;
+        let mask = if width < WORD_LEN {
+            (1 << width) - 1
+        } else {
+            usize::MAX
+        };
+        for (slot, &pos) in out.iter_mut().zip(positions) {
+            *slot = if pos < self.len {
+                let bit = pos * width;
+                let (wpos, shift) = (bit / WORD_LEN, bit % WORD_LEN);
+                let mut x = words[wpos] >> shift;
+                if WORD_LEN < shift + width {
+                    x |= words[wpos + 1] << (WORD_LEN - shift);
+                }
+                Some(x & mask)
+            } else {
+                None
+            };
+        }
+        Ok(())
+    }
+
+    /// Decodes the integers in positions `lo..hi` into a new vector,
+    /// or returns [`None`] if `hi < lo` or `self.len() < hi`.
+    ///
+    /// # Arguments
+    ///
+    ///  - `lo`: First position to decode.
+    ///  - `hi`: Position one past the last one to decode.
+    ///
+    /// # Complexity
+    ///
+    /// $`O(hi - lo)`$
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let cv = CompactVector::from_slice(&[5, 256, 0, 7])?;
+    /// assert_eq!(cv.decode_range(1, 3), Some(vec![256, 0]));
+    /// assert_eq!(cv.decode_range(2, 2), Some(vec![]));
+    /// assert_eq!(cv.decode_range(2, 5), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn decode_range(&self, lo: usize, hi: usize) -> Option<Vec<usize>> {
+        let mut buf = Vec::new();
+        self.decode_range_into(lo, hi, &mut buf)?;
+        Some(buf)
+    }
+
+    /// Decodes the integers in positions `lo..hi` into `buf`,
+    /// or returns [`None`] if `hi < lo` or `self.len() < hi`.
+    ///
+    /// `buf` is cleared before decoding so that its allocation can be reused
+    /// across calls. It is left untouched when [`None`] is returned.
+    ///
+    /// Instead of extracting each integer separately, consecutive machine words
+    /// are read once and shifted through a sliding window.
+    ///
+    /// # Arguments
+    ///
+    ///  - `lo`: First position to decode.
+    ///  - `hi`: Position one past the last one to decode.
+    ///  - `buf`: Buffer receiving the decoded integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let cv = CompactVector::from_slice(&[5, 256, 0, 7])?;
+    /// let mut buf = vec![];
+    /// cv.decode_range_into(0, 2, &mut buf).unwrap();
+    /// assert_eq!(buf, vec![5, 256]);
+    /// cv.decode_range_into(3, 4, &mut buf).unwrap();
+    /// assert_eq!(buf, vec![7]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn decode_range_into(&self, lo: usize, hi: usize, buf: &mut Vec<usize>) -> Option<()> {
+        if hi < lo || self.len() < hi {
+            return None;
+        }
+        buf.clear();
+        if lo == hi {
+            return Some(());
+        }
+        buf.reserve(hi - lo);
+
+        let width = self.width;
+        let mask = if width < WORD_LEN {
+            (1 << width) - 1
+        } else {
+            usize::MAX
+        };
+        let words = self.chunks.data.words();
+        let mut wpos = lo * width / WORD_LEN;
+        let mut shift = lo * width % WORD_LEN;
+        let mut cur = words[wpos];
+        for _ in lo..hi {
+            let val = if shift + width <= WORD_LEN {
+                (cur >> shift) & mask
+            } else {
+                ((cur >> shift) | (words[wpos + 1] << (WORD_LEN - shift))) & mask
+            };
+            buf.push(val);
+            shift += width;
+            if WORD_LEN <= shift {
+                shift -= WORD_LEN;
+                wpos += 1;
+                if wpos < words.len() {
+                    cur = words[wpos];
+                }
+            }
+        }
+        Some(())
+    }
+
+    /// Decodes all integers into `buf`, clearing it first.
+    ///
+    /// When the width divides [`WORD_LEN`] (e.g., 8, 16, or 32), no integer
+    /// straddles a word boundary, so each word is unpacked into several
+    /// integers at once. Other widths fall back to [`Self::get_int`].
+    ///
+    /// # Arguments
+    ///
+    ///  - `buf`: Buffer receiving the decoded integers.
+    ///
+    /// # Complexity
+    ///
+    /// $`O(n)`$ where $`n`$ is the number of integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let cv = CompactVector::from_slice(&[5, 255, 0, 7])?;
+    /// assert_eq!(cv.width(), 8);
+    /// let mut buf = vec![1, 2];
+    /// cv.decode_into(&mut buf);
+    /// assert_eq!(buf, vec![5, 255, 0, 7]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn decode_into(&self, buf: &mut Vec<usize>) {
+        buf.clear();
+        if self.is_empty() {
+            return;
+        }
+        buf.reserve(self.len());
+        if WORD_LEN % self.width == 0 {
+            self.unpack_aligned_into(buf);
+        } else {
+            buf.extend((0..self.len()).map(|pos| self.get_int(pos).unwrap()));
+        }
+    }
+
+    /// Unpacks whole words into `buf`, assuming `self.width` divides [`WORD_LEN`].
+    fn unpack_aligned_into(&self, buf: &mut Vec<usize>) {
+        let width = self.width;
+        debug_assert_eq!(WORD_LEN % width, 0);
+        let per_word = WORD_LEN / width;
+        let mask = if width < WORD_LEN {
+            (1 << width) - 1
+        } else {
+            usize::MAX
+        };
+        let words = self.chunks.data.words();
+        let num_full = self.len() / per_word;
+        for &word in &words[..num_full] {
+            buf.extend((0..per_word).map(|i| (word >> (i * width)) & mask));
+        }
+        let rest = self.len() % per_word;
+        if rest != 0 {
+            let word = words[num_full];
+            buf.extend((0..rest).map(|i| (word >> (i * width)) & mask));
+        }
     }
 
     /// Sets the `pos`-th integer to `val`.
@@ -395,11 +901,78 @@ impl CompactVector {
         Iter::new(self)
     }
 
+    /// Creates a cursor for reading integers that can be peeked
+    /// and moved to any position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let cv = CompactVector::from_slice(&[5, 256, 0])?;
+    /// let mut cursor = cv.cursor();
+    ///
+    /// assert_eq!(cursor.peek(), Some(5));
+    /// assert_eq!(cursor.next(), Some(5));
+    /// assert_eq!(cursor.position(), 1);
+    /// cursor.seek(2);
+    /// assert_eq!(cursor.next(), Some(0));
+    /// assert_eq!(cursor.next(), None);
+    /// cursor.seek(0);
+    /// assert_eq!(cursor.peek(), Some(5));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub const fn cursor(&self) -> Cursor<'_> {
+        Cursor { cv: self, pos: 0 }
+    }
+
     /// Collects all integers into a `Vec<usize>` for inspection.
     pub fn to_vec(&self) -> Vec<usize> {
         self.iter().collect()
     }
 
+    /// Creates an iterator over the gaps `self[i + 1] - self[i]`,
+    /// assuming the integers are non-decreasing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let cv = CompactVector::from_slice(&[2, 5, 5, 9])?;
+    /// assert_eq!(cv.gaps().collect::<Vec<_>>(), vec![3, 0, 4]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub const fn gaps(&self) -> Gaps<Iter<'_>> {
+        Gaps::new(self.iter())
+    }
+
+    /// Creates an iterator for enumerating pairs of positions and integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let cv = CompactVector::from_slice(&[5, 256, 0])?;
+    /// let mut it = cv.enumerate_vals();
+    ///
+    /// assert_eq!(it.next(), Some((0, 5)));
+    /// assert_eq!(it.next(), Some((1, 256)));
+    /// assert_eq!(it.next(), Some((2, 0)));
+    /// assert_eq!(it.next(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn enumerate_vals(&self) -> core::iter::Enumerate<Iter<'_>> {
+        self.iter().enumerate()
+    }
+
     /// Gets the number of integers.
     #[inline(always)]
     pub const fn len(&self) -> usize {
@@ -423,6 +996,48 @@ impl CompactVector {
         self.width
     }
 
+    /// Checks the internal invariants, e.g., of a vector loaded by [`Self::from_bytes`]
+    /// from untrusted bytes.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if
+    ///
+    ///  - the width is not in `1..=64`,
+    ///  - the number of bits stored is not `self.len() * self.width()`, or
+    ///  - the words hold fewer bits or have a bit set beyond them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::CompactVector;
+    ///
+    /// let cv = CompactVector::from_slice(&[5, 256, 0])?;
+    /// cv.validate()?;
+    ///
+    /// let (mut meta, bytes) = cv.to_bytes();
+    /// meta.len = 1;
+    /// let truncated = CompactVector::from_bytes(meta, bytes)?;
+    /// assert!(truncated.validate().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=64).contains(&self.width) {
+            return Err(anyhow!("width must be in 1..=64, but got {}.", self.width));
+        }
+        let num_bits = self.chunks.len();
+        if Some(num_bits) != self.len.checked_mul(self.width) {
+            return Err(anyhow!(
+                "the number of bits must be len * width = {} * {}, but got {num_bits}.",
+                self.len,
+                self.width
+            ));
+        }
+        self.chunks.data.validate()
+    }
+
     /// Serializes the vector into a [`Bytes`] buffer and accompanying metadata.
     pub fn to_bytes(&self) -> (CompactVectorMeta, Bytes) {
         let (_, bytes) = self.chunks.data.to_bytes();
@@ -436,8 +1051,22 @@ impl CompactVector {
     }
 
     /// Reconstructs the vector from zero-copy [`Bytes`] and its metadata.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `meta.width` is not in `1..=64`
+    /// or `bytes` holds fewer than `meta.len * meta.width` bits.
     pub fn from_bytes(meta: CompactVectorMeta, bytes: Bytes) -> Result<Self> {
-        let data_len = meta.len * meta.width;
+        if !(1..=64).contains(&meta.width) {
+            return Err(anyhow!("width must be in 1..=64, but got {}.", meta.width));
+        }
+        let data_len = meta.len.checked_mul(meta.width).ok_or_else(|| {
+            anyhow!(
+                "meta.len * meta.width must fit in usize, but got {} * {}.",
+                meta.len,
+                meta.width
+            )
+        })?;
         let data = BitVectorData::from_bytes(data_len, bytes)?;
         let chunks = BitVector::new(data, NoIndex);
         Ok(Self {
@@ -531,6 +1160,57 @@ impl Iterator for Iter<'_> {
     }
 }
 
+/// Cursor for reading integers with peeking and seeking,
+/// created by [`CompactVector::cursor()`].
+///
+/// Unlike [`Iter`], the cursor can move backward or forward with [`Self::seek`],
+/// which reads integers directly by position.
+pub struct Cursor<'a> {
+    cv: &'a CompactVector,
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    /// Returns the integer that the next call of [`Iterator::next`] yields,
+    /// without advancing the cursor.
+    #[inline(always)]
+    pub fn peek(&self) -> Option<usize> {
+        self.cv.get_int(self.pos)
+    }
+
+    /// Moves the cursor to `pos`.
+    ///
+    /// A position at or beyond the end is accepted,
+    /// after which the cursor yields [`None`].
+    #[inline(always)]
+    pub fn seek(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+
+    /// Returns the position of the integer yielded next.
+    #[inline(always)]
+    pub const fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+impl Iterator for Cursor<'_> {
+    type Item = usize;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.peek()?;
+        self.pos += 1;
+        Some(x)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.cv.len().saturating_sub(self.pos);
+        (rest, Some(rest))
+    }
+}
+
 impl std::fmt::Debug for CompactVector {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut ints = vec![0; self.len()];
@@ -548,6 +1228,8 @@ impl std::fmt::Debug for CompactVector {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
 
     #[test]
     fn test_new_oob_0() {
@@ -585,6 +1267,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_with_capacity_overflow() {
+        let e = CompactVector::with_capacity(usize::MAX / 3 + 1, 3);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some(format!(
+                "capa must be no greater than {}, but got {}.",
+                usize::MAX / 3,
+                usize::MAX / 3 + 1
+            ))
+        );
+        assert!(CompactVector::from_int(0, usize::MAX, 2).is_err());
+    }
+
     #[test]
     fn test_from_int_oob_0() {
         let e = CompactVector::from_int(0, 0, 0);
@@ -621,6 +1317,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_slice_with_width() {
+        for width in [3, 8, 33, 64] {
+            let cv = CompactVector::from_slice_with_width(&[7u32, 0, 5], width).unwrap();
+            assert_eq!(cv.width(), width);
+            assert_eq!(cv.to_vec(), vec![7, 0, 5]);
+        }
+        let cv = CompactVector::from_slice_with_width::<u32>(&[], 5).unwrap();
+        assert_eq!(cv.width(), 5);
+        assert!(cv.is_empty());
+    }
+
+    #[test]
+    fn test_from_slice_with_width_unfit() {
+        let e = CompactVector::from_slice_with_width(&[1, 4], 2);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("val must fit in self.width()=2 bits, but got 4.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_from_slice_with_width_oob() {
+        let e = CompactVector::from_slice_with_width(&[1], 65);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("width must be in 1..=64, but got 65.".to_string())
+        );
+    }
+
     #[test]
     fn test_set_int_oob() {
         let mut builder = CompactVectorBuilder::with_capacity(1, 2).unwrap();
@@ -679,6 +1405,54 @@ mod tests {
         assert_eq!(cv.get_int(0), Some(42));
     }
 
+    #[test]
+    fn test_default_is_empty() {
+        let cv = CompactVector::default();
+        assert_eq!(cv.width(), 1);
+        assert_eq!(cv.len(), 0);
+        assert_eq!(cv.get_int(0), None);
+        assert_eq!(cv.get_int(1), None);
+        assert_eq!(CompactVector::from_slice::<u32>(&[]).unwrap(), cv);
+        let mut buf = vec![1];
+        cv.decode_into(&mut buf);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_get_int_overflow() {
+        let cv = CompactVector::from_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(cv.get_int(usize::MAX), None);
+        assert_eq!(cv.get_int(usize::MAX / 2 + 1), None);
+    }
+
+    #[test]
+    fn test_empty_slice() {
+        let cv = CompactVector::from_slice::<usize>(&[]).unwrap();
+        assert_eq!(cv, CompactVector::default());
+        assert!(cv.is_empty());
+        assert_eq!(cv.width(), 1);
+        let e = CompactVector::try_from_slice::<usize>(&[]);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("vals must not be empty.".to_string())
+        );
+        assert_eq!(
+            CompactVector::try_from_slice(&[0]).unwrap(),
+            CompactVector::from_slice(&[0]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_int_or() {
+        let cv = CompactVector::from_slice(&[1, 0, 3]).unwrap();
+        assert_eq!(cv.get_int_or(0, 9), 1);
+        assert_eq!(cv.get_int_or(1, 9), 0);
+        assert_eq!(cv.get_int_or(2, 9), 3);
+        assert_eq!(cv.get_int_or(3, 9), 9);
+        assert_eq!(cv.get_int_or(usize::MAX, 9), 9);
+        assert_eq!(CompactVector::default().get_int_or(0, 4), 4);
+    }
+
     #[test]
     fn iter_collects() {
         let cv = CompactVector::from_slice(&[1, 2, 3]).unwrap();
@@ -692,6 +1466,72 @@ mod tests {
         assert_eq!(cv.to_vec(), vec![1, 2, 3]);
     }
 
+    #[test]
+    fn enumerate_vals_matches_iter() {
+        let cv = CompactVector::from_slice(&[1, 0, 7, 3]).unwrap();
+        let pairs: Vec<(usize, usize)> = cv.enumerate_vals().collect();
+        assert_eq!(pairs, cv.iter().enumerate().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_decode_range() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for width in [1, 3, 7, 8, 13, 31, 32, 33, 63, 64] {
+            let mut builder = CompactVectorBuilder::new(width).unwrap();
+            for _ in 0..300 {
+                let val: usize = rng.gen();
+                builder
+                    .push_int(if width < 64 { val >> (64 - width) } else { val })
+                    .unwrap();
+            }
+            let cv = builder.freeze();
+            let expected: Vec<usize> = (0..cv.len()).map(|i| cv.get_int(i).unwrap()).collect();
+
+            assert_eq!(cv.decode_range(0, cv.len()), Some(expected.clone()));
+            assert_eq!(cv.decode_range(cv.len(), cv.len()), Some(vec![]));
+            assert_eq!(cv.decode_range(0, cv.len() + 1), None);
+            assert_eq!(cv.decode_range(2, 1), None);
+
+            let mut buf = vec![];
+            for _ in 0..100 {
+                let lo = rng.gen_range(0..=cv.len());
+                let hi = rng.gen_range(lo..=cv.len());
+                cv.decode_range_into(lo, hi, &mut buf).unwrap();
+                assert_eq!(buf, expected[lo..hi]);
+            }
+            let before = buf.clone();
+            assert_eq!(cv.decode_range_into(0, cv.len() + 1, &mut buf), None);
+            assert_eq!(cv.decode_range_into(2, 1, &mut buf), None);
+            assert_eq!(buf, before);
+        }
+    }
+
+    #[test]
+    fn test_decode_into_all_widths() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        let mut buf = vec![];
+        for width in 1..=64 {
+            for len in [0, 1, 63, 64, 65, 200] {
+                let mut builder = CompactVectorBuilder::new(width).unwrap();
+                for _ in 0..len {
+                    let val: usize = rng.gen();
+                    builder
+                        .push_int(if width < 64 { val >> (64 - width) } else { val })
+                        .unwrap();
+                }
+                let cv = builder.freeze();
+                let expected: Vec<usize> = (0..len).map(|i| cv.get_int(i).unwrap()).collect();
+                cv.decode_into(&mut buf);
+                assert_eq!(buf, expected);
+                if WORD_LEN % width == 0 {
+                    buf.clear();
+                    cv.unpack_aligned_into(&mut buf);
+                    assert_eq!(buf, expected);
+                }
+            }
+        }
+    }
+
     #[test]
     fn from_bytes_roundtrip() {
         let cv = CompactVector::from_slice(&[4, 5, 6]).unwrap();
@@ -699,4 +1539,211 @@ mod tests {
         let other = CompactVector::from_bytes(meta, bytes).unwrap();
         assert_eq!(cv, other);
     }
+
+    #[test]
+    fn test_builder_clone_is_independent() {
+        let mut builder = CompactVectorBuilder::new(4).unwrap();
+        builder.extend([1, 2, 3]).unwrap();
+        let mut clone = builder.clone();
+        let frozen = builder.freeze();
+        clone.set_int(0, 9).unwrap();
+        clone.push_int(15).unwrap();
+        assert_eq!(frozen.to_vec(), vec![1, 2, 3]);
+        assert_eq!(clone.freeze().to_vec(), vec![9, 2, 3, 15]);
+    }
+
+    #[test]
+    fn test_count_distinct() {
+        assert_eq!(CompactVector::default().count_distinct(), 0);
+        let cv = CompactVector::from_slice(&[7, 7, 7]).unwrap();
+        assert_eq!(cv.count_distinct(), 1);
+
+        let full: Vec<usize> = (0..256).chain((0..256).rev()).collect();
+        let cv = CompactVector::from_slice_with_width(&full, 8).unwrap();
+        assert_eq!(cv.count_distinct(), 256);
+
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for width in [1, 5, 24, 25, 40, 64] {
+            let max = if width == 64 {
+                usize::MAX
+            } else {
+                (1 << width) - 1
+            };
+            let pool: Vec<usize> = (0..50).map(|_| rng.gen_range(0..=max)).collect();
+            let vals: Vec<usize> = (0..500).map(|_| pool[rng.gen_range(0..50)]).collect();
+            let cv = CompactVector::from_slice_with_width(&vals, width).unwrap();
+            let expected = vals.iter().collect::<std::collections::HashSet<_>>().len();
+            assert_eq!(cv.count_distinct(), expected);
+        }
+    }
+
+    #[test]
+    fn test_repack_minimal() {
+        let vals = [0, 200, 17, 255, 3];
+        let cv = CompactVector::from_slice_with_width(&vals, 40).unwrap();
+        let packed = cv.repack_minimal();
+        assert_eq!(packed.width(), 8);
+        assert_eq!(packed.to_vec(), cv.to_vec());
+        assert_eq!(packed, CompactVector::from_slice(&vals).unwrap());
+        assert_eq!(packed.repack_minimal(), packed);
+
+        let zeros = CompactVector::from_int(0, 10, 64).unwrap().repack_minimal();
+        assert_eq!(zeros.width(), 1);
+        assert_eq!(zeros.to_vec(), vec![0; 10]);
+
+        let full = CompactVector::from_slice(&[usize::MAX, 1]).unwrap();
+        assert_eq!(full.repack_minimal(), full);
+        assert_eq!(
+            CompactVector::new(7).unwrap().freeze().repack_minimal(),
+            CompactVector::default()
+        );
+    }
+
+    #[test]
+    fn test_cumulative_select() {
+        let counts = [0, 3, 0, 0, 1, 2, 0];
+        let cv = CompactVector::from_slice(&counts).unwrap();
+        let mut expected = vec![];
+        for (pos, &c) in counts.iter().enumerate() {
+            expected.extend(std::iter::repeat(pos).take(c));
+        }
+        for (k, &pos) in expected.iter().enumerate() {
+            assert_eq!(cv.cumulative_select(k), Some(pos));
+        }
+        assert_eq!(cv.cumulative_select(expected.len()), None);
+        assert_eq!(CompactVector::default().cumulative_select(0), None);
+
+        let cv = CompactVector::from_slice(&[usize::MAX, usize::MAX, 1]).unwrap();
+        assert_eq!(cv.cumulative_select(usize::MAX - 1), Some(0));
+        assert_eq!(cv.cumulative_select(usize::MAX), Some(1));
+    }
+
+    #[test]
+    fn test_access_many() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for width in [1, 3, 7, 8, 13, 32, 63, 64] {
+            let max = if width == 64 {
+                usize::MAX
+            } else {
+                (1 << width) - 1
+            };
+            let vals: Vec<usize> = (0..200).map(|_| rng.gen_range(0..=max)).collect();
+            let cv = CompactVector::from_slice_with_width(&vals, width).unwrap();
+            let positions: Vec<usize> = (0..500).map(|_| rng.gen_range(0..250)).collect();
+            let mut out = vec![None; positions.len()];
+            cv.access_many(&positions, &mut out).unwrap();
+            let expected: Vec<_> = positions.iter().map(|&p| cv.get_int(p)).collect();
+            assert_eq!(out, expected);
+        }
+        let cv = CompactVector::from_slice(&[1, 2]).unwrap();
+        let e = cv.access_many(&[0, 1], &mut [None]);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("out must have the same length as positions, but got 1 and 2.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cursor() {
+        let vals = [3, 1, 4, 1, 5, 9, 2, 6];
+        let cv = CompactVector::from_slice(&vals).unwrap();
+        let mut cursor = cv.cursor();
+        for (i, &x) in vals.iter().enumerate() {
+            assert_eq!(cursor.position(), i);
+            assert_eq!(cursor.peek(), Some(x));
+            assert_eq!(cursor.peek(), Some(x));
+            assert_eq!(cursor.next(), Some(x));
+        }
+        assert_eq!(cursor.peek(), None);
+        assert_eq!(cursor.next(), None);
+        assert_eq!(cursor.position(), vals.len());
+
+        cursor.seek(2);
+        assert_eq!(cursor.size_hint(), (6, Some(6)));
+        assert_eq!(cursor.by_ref().take(2).collect::<Vec<_>>(), vec![4, 1]);
+        cursor.seek(6);
+        assert_eq!(cursor.next(), Some(2));
+        cursor.seek(1);
+        assert_eq!(cursor.collect::<Vec<_>>(), vals[1..]);
+
+        let mut cursor = cv.cursor();
+        cursor.seek(100);
+        assert_eq!(cursor.size_hint(), (0, Some(0)));
+        assert_eq!(cursor.peek(), None);
+        assert_eq!(cursor.next(), None);
+        assert_eq!(cursor.position(), 100);
+    }
+
+    #[test]
+    fn test_validate() {
+        let cv = CompactVector::from_slice(&[5, 256, 0, 7]).unwrap();
+        cv.validate().unwrap();
+        CompactVector::default().validate().unwrap();
+        let (meta, bytes) = cv.to_bytes();
+        CompactVector::from_bytes(meta, bytes.clone())
+            .unwrap()
+            .validate()
+            .unwrap();
+
+        let short = CompactVectorMeta { len: 2, ..meta };
+        let cv = CompactVector::from_bytes(short, bytes.clone()).unwrap();
+        assert!(cv.validate().is_err());
+
+        let zero = CompactVectorMeta { len: 3, width: 0 };
+        let e = CompactVector::from_bytes(zero, Bytes::empty());
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("width must be in 1..=64, but got 0.".to_string())
+        );
+        let wide = CompactVectorMeta { len: 0, width: 65 };
+        assert!(CompactVector::from_bytes(wide, Bytes::empty()).is_err());
+    }
+
+    #[test]
+    fn test_value_eq() {
+        let vals = [4, 0, 7, 3, 1];
+        let a = CompactVector::from_slice_with_width(&vals, 3).unwrap();
+        let b = CompactVector::from_slice_with_width(&vals, 5).unwrap();
+        assert!(a.value_eq(&b));
+        assert!(b.value_eq(&a));
+        assert_ne!(a, b);
+        assert!(a.value_eq(&a.clone()));
+
+        let c = CompactVector::from_slice_with_width(&[4, 0, 7, 3, 2], 5).unwrap();
+        assert!(!a.value_eq(&c));
+        let d = CompactVector::from_slice_with_width(&vals[..4], 3).unwrap();
+        assert!(!a.value_eq(&d));
+        assert!(CompactVector::default().value_eq(&CompactVector::new(7).unwrap().freeze()));
+
+        for vals in [&[4, 0, 7, 3, 1][..], &[1; 64][..]] {
+            let cv = CompactVector::from_slice_with_width(vals, 3).unwrap();
+            let (meta, _) = cv.to_bytes();
+            let mut words = cv.chunks.data.words().to_vec();
+            words.push(0);
+            let longer = CompactVector::from_bytes(meta, Bytes::from_source(words)).unwrap();
+            longer.validate().unwrap();
+            assert!(cv.value_eq(&longer));
+            assert!(longer.value_eq(&cv));
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_too_short() {
+        let meta = CompactVectorMeta { len: 3, width: 30 };
+        let e = CompactVector::from_bytes(meta, Bytes::from_source(vec![0usize]));
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("bytes must hold at least len=90 bits, but got 64 bits.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_overflow() {
+        let meta = CompactVectorMeta {
+            len: usize::MAX,
+            width: 2,
+        };
+        let e = CompactVector::from_bytes(meta, Bytes::empty());
+        assert!(e.is_err());
+    }
 }
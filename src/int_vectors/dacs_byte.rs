@@ -6,8 +6,11 @@ use std::convert::TryFrom;
 use anyhow::{anyhow, Result};
 use num_traits::ToPrimitive;
 
-use crate::bit_vector::{self, BitVector, BitVectorBuilder, BitVectorIndex, Rank, Rank9SelIndex};
-use crate::int_vectors::{Access, Build, NumVals};
+use crate::bit_vector::{
+    self, BitVector, BitVectorBuilder, BitVectorData, BitVectorIndex, Rank, Rank9SelIndex,
+};
+use crate::int_vectors::compact_vector::CompactVectorMeta;
+use crate::int_vectors::{Access, Build, CompactVector, NumVals};
 use crate::utils;
 use anybytes::{Bytes, View};
 
@@ -105,6 +108,9 @@ impl Default for DacsByteMeta {
 impl<I: BitVectorIndex> DacsByte<I> {
     /// Builds DACs by assigning 8 bits to represent each level.
     ///
+    /// An empty `vals` gives an empty sequence equal to [`Self::default()`];
+    /// use [`Self::try_from_slice`] to reject it instead.
+    ///
     /// # Arguments
     ///
     /// - `vals`: Slice of integers to be stored.
@@ -120,15 +126,20 @@ impl<I: BitVectorIndex> DacsByte<I> {
             return Ok(Self::default());
         }
 
-        let mut maxv = 0;
+        // level_lens[j] counts the integers reaching level j,
+        // which is exactly the capacity needed for that level.
+        // It first counts the integers ending at level j and is then suffix-summed.
+        let mut level_lens = [0; MAX_LEVELS];
         for x in vals {
-            maxv =
-                maxv.max(x.to_usize().ok_or_else(|| {
-                    anyhow!("vals must consist only of values castable into usize.")
-                })?);
+            let x = x
+                .to_usize()
+                .ok_or_else(|| anyhow!("vals must consist only of values castable into usize."))?;
+            level_lens[(utils::needed_bits(x) - 1) / LEVEL_WIDTH] += 1;
+        }
+        for j in (1..MAX_LEVELS).rev() {
+            level_lens[j - 1] += level_lens[j];
         }
-        let num_bits = utils::needed_bits(maxv);
-        let num_levels = utils::ceiled_divide(num_bits, LEVEL_WIDTH);
+        let num_levels = level_lens.iter().take_while(|&&len| len != 0).count();
         assert_ne!(num_levels, 0);
 
         if num_levels == 1 {
@@ -145,8 +156,14 @@ impl<I: BitVectorIndex> DacsByte<I> {
             });
         }
 
-        let mut level_data = vec![vec![]; num_levels];
-        let mut flag_builders = vec![BitVectorBuilder::new(); num_levels - 1];
+        let mut level_data: Vec<Vec<u8>> = level_lens[..num_levels]
+            .iter()
+            .map(|&len| Vec::with_capacity(len))
+            .collect();
+        let mut flag_builders: Vec<BitVectorBuilder> = level_lens[..num_levels - 1]
+            .iter()
+            .map(|&len| BitVectorBuilder::with_capacity(len))
+            .collect();
 
         for x in vals {
             let mut x = x.to_usize().unwrap();
@@ -157,10 +174,10 @@ impl<I: BitVectorIndex> DacsByte<I> {
                     assert_eq!(x, 0);
                     break;
                 } else if x == 0 {
-                    flag_builders[j].push_bit(false);
+                    flag_builders[j].push_bit(false)?;
                     break;
                 }
-                flag_builders[j].push_bit(true);
+                flag_builders[j].push_bit(true)?;
             }
         }
 
@@ -176,8 +193,6 @@ impl<I: BitVectorIndex> DacsByte<I> {
             flag_bytes.push(bytes);
         }
 
-        let level_lens: Vec<usize> = level_data.iter().map(|v| v.len()).collect();
-
         let total_flags: usize = flag_info.iter().map(|(_, w)| w * usize_size).sum();
         let total_levels: usize = level_lens.iter().sum();
         let mut buf = Vec::with_capacity(total_flags + total_levels);
@@ -211,7 +226,7 @@ impl<I: BitVectorIndex> DacsByte<I> {
         }
 
         let mut data = Vec::with_capacity(level_offsets.len());
-        for (offset, len) in level_offsets.into_iter().zip(level_lens) {
+        for (offset, &len) in level_offsets.into_iter().zip(&level_lens[..num_levels]) {
             let start = offset;
             let end = start + len;
             let view_bytes = bytes
@@ -253,6 +268,129 @@ impl<I: BitVectorIndex> DacsByte<I> {
         self.iter().collect()
     }
 
+    /// Creates an iterator for enumerating pairs of positions and integers.
+    ///
+    /// Like [`Self::iter()`], the iterator walks the levels sequentially
+    /// and does not issue rank queries on the flag vectors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::rank9sel::Rank9SelIndex;
+    /// use jerky::int_vectors::DacsByte;
+    ///
+    /// let seq = DacsByte::<Rank9SelIndex>::from_slice(&[5, 0, 100000, 334])?;
+    /// let mut it = seq.enumerate_vals();
+    ///
+    /// assert_eq!(it.next(), Some((0, 5)));
+    /// assert_eq!(it.next(), Some((1, 0)));
+    /// assert_eq!(it.next(), Some((2, 100000)));
+    /// assert_eq!(it.next(), Some((3, 334)));
+    /// assert_eq!(it.next(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub const fn enumerate_vals(&self) -> EnumerateVals<'_, I> {
+        EnumerateVals::new(self)
+    }
+
+    /// Decodes the integers in positions `lo..hi` into a new vector,
+    /// or returns [`None`] if `hi < lo` or `self.len() < hi`.
+    ///
+    /// # Arguments
+    ///
+    /// - `lo`: First position to decode.
+    /// - `hi`: Position one past the last one to decode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::rank9sel::Rank9SelIndex;
+    /// use jerky::int_vectors::DacsByte;
+    ///
+    /// let seq = DacsByte::<Rank9SelIndex>::from_slice(&[5, 0, 100000, 334])?;
+    /// assert_eq!(seq.decode_range(1, 3), Some(vec![0, 100000]));
+    /// assert_eq!(seq.decode_range(4, 4), Some(vec![]));
+    /// assert_eq!(seq.decode_range(3, 5), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn decode_range(&self, lo: usize, hi: usize) -> Option<Vec<usize>> {
+        let mut buf = Vec::new();
+        self.decode_range_into(lo, hi, &mut buf)?;
+        Some(buf)
+    }
+
+    /// Decodes the integers in positions `lo..hi` into `buf`,
+    /// or returns [`None`] if `hi < lo` or `self.len() < hi`.
+    ///
+    /// `buf` is cleared before decoding so that its allocation can be reused
+    /// across calls. It is left untouched when [`None`] is returned.
+    ///
+    /// Rank queries on the flag vectors are only issued once to locate `lo`
+    /// in each level; the remaining integers are decoded sequentially.
+    ///
+    /// # Arguments
+    ///
+    /// - `lo`: First position to decode.
+    /// - `hi`: Position one past the last one to decode.
+    /// - `buf`: Buffer receiving the decoded integers.
+    pub fn decode_range_into(&self, lo: usize, hi: usize, buf: &mut Vec<usize>) -> Option<()> {
+        if hi < lo || self.len() < hi {
+            return None;
+        }
+        let mut cursors = [0; MAX_LEVELS];
+        cursors[0] = lo;
+        for (j, flag) in self.flags.iter().enumerate() {
+            cursors[j + 1] = flag.rank1(cursors[j])?;
+        }
+        buf.clear();
+        buf.reserve(hi - lo);
+        for _ in lo..hi {
+            buf.push(self.decode_next(&mut cursors));
+        }
+        Some(())
+    }
+
+    /// Builds DACs from a non-empty slice of integers `vals`
+    /// in the same way as [`Self::from_slice`].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `vals` is empty or contains an integer
+    /// that cannot be cast to [`usize`].
+    pub fn try_from_slice<T>(vals: &[T]) -> Result<Self>
+    where
+        T: ToPrimitive,
+    {
+        if vals.is_empty() {
+            return Err(anyhow!("vals must not be empty."));
+        }
+        Self::from_slice(vals)
+    }
+
+    /// Returns the `pos`-th integer, or `default` if out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::rank9sel::Rank9SelIndex;
+    /// use jerky::int_vectors::DacsByte;
+    ///
+    /// let seq = DacsByte::<Rank9SelIndex>::from_slice(&[5, 999, 334])?;
+    /// assert_eq!(seq.access_or(1, 7), 999);
+    /// assert_eq!(seq.access_or(3, 7), 7);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn access_or(&self, pos: usize, default: usize) -> usize {
+        self.access(pos).unwrap_or(default)
+    }
+
     /// Gets the number of integers.
     #[inline(always)]
     pub fn len(&self) -> usize {
@@ -277,6 +415,54 @@ impl<I: BitVectorIndex> DacsByte<I> {
         self.data.iter().map(|_| LEVEL_WIDTH).collect()
     }
 
+    /// Converts the sequence into a [`CompactVector`] of width 8
+    /// if all integers fit in one level, or returns [`None`] otherwise.
+    ///
+    /// The resulting vector drops the overhead of the flag vectors.
+    /// The bytes of the level are viewed without copying when their alignment allows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::bit_vector::rank9sel::Rank9SelIndex;
+    /// use jerky::int_vectors::DacsByte;
+    ///
+    /// let seq = DacsByte::<Rank9SelIndex>::from_slice(&[5, 255, 0])?;
+    /// let cv = seq.try_into_compact().unwrap();
+    /// assert_eq!(cv.width(), 8);
+    /// assert_eq!(cv.to_vec(), vec![5, 255, 0]);
+    ///
+    /// let seq = DacsByte::<Rank9SelIndex>::from_slice(&[5, 256])?;
+    /// assert_eq!(seq.try_into_compact(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_into_compact(&self) -> Option<CompactVector> {
+        if self.num_levels() != 1 {
+            return None;
+        }
+        let len = self.len();
+        let level = self.data[0].clone().bytes();
+        let data = BitVectorData::from_packed_bytes(level, len * LEVEL_WIDTH).ok()?;
+        let meta = CompactVectorMeta {
+            len,
+            width: LEVEL_WIDTH,
+        };
+        CompactVector::from_bytes(meta, data.to_bytes().1).ok()
+    }
+
+    /// Gets the total number of bytes stored over all levels.
+    pub fn total_level_bytes(&self) -> usize {
+        self.data.iter().map(|level| level.len()).sum()
+    }
+
+    /// Gets the total number of bits of the levels and the flag vectors,
+    /// excluding the indexes on the flags.
+    pub fn num_bits_total(&self) -> usize {
+        self.total_level_bytes() * LEVEL_WIDTH + self.flags.iter().map(|f| f.len()).sum::<usize>()
+    }
+
     /// Serializes the sequence into a [`Bytes`] buffer.
     ///
     /// Returns the metadata necessary for [`from_bytes`].
@@ -325,16 +511,11 @@ impl<I: BitVectorIndex> DacsByte<I> {
             if cursor + bytes_len > slice.len() {
                 return Err(anyhow!("insufficient bytes"));
             }
-            let words_view = bytes
+            let flag_bytes = bytes
                 .slice_to_bytes(&slice[cursor..cursor + bytes_len])
-                .ok_or_else(|| anyhow!("invalid slice"))?
-                .view::<[usize]>()
-                .map_err(|e| anyhow!(e))?;
+                .ok_or_else(|| anyhow!("invalid slice"))?;
             cursor += bytes_len;
-            let data = bit_vector::BitVectorData {
-                words: words_view,
-                len: fm.len_bits,
-            };
+            let data = bit_vector::BitVectorData::from_bytes(fm.len_bits, flag_bytes)?;
             let index = I::build(&data);
             flags.push(bit_vector::BitVector { data, index });
         }
@@ -356,6 +537,27 @@ impl<I: BitVectorIndex> DacsByte<I> {
     }
 }
 
+impl<I> DacsByte<I> {
+    /// Decodes the integer whose level-0 byte is at `cursors[0]`.
+    ///
+    /// `cursors[j]` must point to the next unread byte in level `j`;
+    /// the cursors of all levels visited are advanced by one.
+    #[inline(always)]
+    fn decode_next(&self, cursors: &mut [usize; MAX_LEVELS]) -> usize {
+        let num_levels = self.data.len();
+        let mut x = 0;
+        for (j, (level, cursor)) in self.data.iter().zip(cursors.iter_mut()).enumerate() {
+            let idx = *cursor;
+            *cursor += 1;
+            x |= usize::from(level[idx]) << (j * LEVEL_WIDTH);
+            if j == num_levels - 1 || !bit_vector::Access::access(&self.flags[j], idx).unwrap() {
+                break;
+            }
+        }
+        x
+    }
+}
+
 impl<I: BitVectorIndex> Default for DacsByte<I> {
     fn default() -> Self {
         Self {
@@ -416,29 +618,79 @@ impl<I: BitVectorIndex> Access for DacsByte<I> {
             return None;
         }
         let mut x = 0;
+        // Lookups are checked so that a malformed sequence, e.g., one deserialized
+        // from inconsistent metadata, yields None instead of panicking.
         for j in 0..self.num_levels() {
-            x |= usize::from(self.data[j][pos]) << (j * LEVEL_WIDTH);
-            if j == self.num_levels() - 1
-                || !bit_vector::Access::access(&self.flags[j], pos).unwrap()
-            {
+            x |= usize::from(*self.data[j].get(pos)?) << (j * LEVEL_WIDTH);
+            if j == self.num_levels() - 1 {
+                break;
+            }
+            let (rank, flag) = self.flags.get(j)?.rank1_and_access(pos)?;
+            if !flag {
                 break;
             }
-            pos = self.flags[j].rank1(pos).unwrap();
+            pos = rank;
         }
         Some(x)
     }
 }
 
 /// Iterator for enumerating integers, created by [`DacsByte::iter()`].
+///
+/// A cursor per level is advanced instead of remapping each position
+/// with rank queries, so enumerating all integers takes time linear
+/// in the number of stored bytes.
 pub struct Iter<'a, I> {
     seq: &'a DacsByte<I>,
-    pos: usize,
+    cursors: [usize; MAX_LEVELS],
 }
 
 impl<'a, I> Iter<'a, I> {
     /// Creates a new iterator.
     pub const fn new(seq: &'a DacsByte<I>) -> Self {
-        Self { seq, pos: 0 }
+        Self {
+            seq,
+            cursors: [0; MAX_LEVELS],
+        }
+    }
+}
+
+/// Iterator for enumerating pairs of positions and integers,
+/// created by [`DacsByte::enumerate_vals()`].
+///
+/// Integers reaching a level are stored there in the order of their positions,
+/// so a cursor per level replaces the rank queries of [`Access::access`].
+pub struct EnumerateVals<'a, I> {
+    seq: &'a DacsByte<I>,
+    cursors: [usize; MAX_LEVELS],
+}
+
+impl<'a, I> EnumerateVals<'a, I> {
+    /// Creates a new iterator.
+    pub const fn new(seq: &'a DacsByte<I>) -> Self {
+        Self {
+            seq,
+            cursors: [0; MAX_LEVELS],
+        }
+    }
+}
+
+impl<I> Iterator for EnumerateVals<'_, I> {
+    type Item = (usize, usize);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.cursors[0];
+        if self.seq.data[0].len() <= pos {
+            return None;
+        }
+        Some((pos, self.seq.decode_next(&mut self.cursors)))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.seq.data[0].len() - self.cursors[0];
+        (rest, Some(rest))
     }
 }
 
@@ -457,10 +709,8 @@ impl<I: BitVectorIndex> Iterator for Iter<'_, I> {
 
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.pos < self.seq.len() {
-            let x = self.seq.access(self.pos).unwrap();
-            self.pos += 1;
-            Some(x)
+        if self.cursors[0] < self.seq.len() {
+            Some(self.seq.decode_next(&mut self.cursors))
         } else {
             None
         }
@@ -468,7 +718,8 @@ impl<I: BitVectorIndex> Iterator for Iter<'_, I> {
 
     #[inline(always)]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.seq.len(), Some(self.seq.len()))
+        let rest = self.seq.len() - self.cursors[0];
+        (rest, Some(rest))
     }
 }
 
@@ -476,6 +727,8 @@ impl<I: BitVectorIndex> Iterator for Iter<'_, I> {
 mod tests {
     use super::*;
     use anybytes::Bytes;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
 
     #[test]
     fn test_basic() {
@@ -494,10 +747,10 @@ mod tests {
         assert_eq!(seq.data, expected);
 
         let mut b = BitVectorBuilder::new();
-        b.extend_bits([true, false, false, true, false]);
+        b.extend_bits([true, false, false, true, false]).unwrap();
         let f0 = b.freeze::<Rank9SelIndex<true, true>>();
         let mut b = BitVectorBuilder::new();
-        b.extend_bits([false, true]);
+        b.extend_bits([false, true]).unwrap();
         let f1 = b.freeze::<Rank9SelIndex<true, true>>();
         assert_eq!(seq.flags, vec![f0, f1]);
 
@@ -513,6 +766,20 @@ mod tests {
         assert_eq!(seq.access(4), Some(0xF));
     }
 
+    #[test]
+    fn test_totals() {
+        let seq =
+            DacsByte::<Rank9SelIndex>::from_slice(&[0xFFFF, 0xFF, 0xF, 0xFFFFF, 0xF]).unwrap();
+        let level_bytes: usize = seq.data.iter().map(|level| level.len()).sum();
+        assert_eq!(level_bytes, 5 + 2 + 1);
+        assert_eq!(seq.total_level_bytes(), level_bytes);
+        assert_eq!(seq.num_bits_total(), level_bytes * 8 + 5 + 2);
+
+        let seq = DacsByte::<Rank9SelIndex>::from_slice::<u32>(&[]).unwrap();
+        assert_eq!(seq.total_level_bytes(), 0);
+        assert_eq!(seq.num_bits_total(), 0);
+    }
+
     #[test]
     fn test_empty() {
         let seq = DacsByte::<Rank9SelIndex>::from_slice::<usize>(&[]).unwrap();
@@ -522,6 +789,50 @@ mod tests {
         assert_eq!(seq.widths(), vec![LEVEL_WIDTH]);
     }
 
+    #[test]
+    fn test_access_or() {
+        let vals = [0, 255, 256, 65535, 65536, 1 << 40];
+        let seq = DacsByte::<Rank9SelIndex>::from_slice(&vals).unwrap();
+        for (i, &x) in vals.iter().enumerate() {
+            assert_eq!(seq.access_or(i, 7), x);
+        }
+        assert_eq!(seq.access_or(vals.len(), 7), 7);
+        assert_eq!(seq.access_or(usize::MAX, 0), 0);
+        assert_eq!(DacsByte::<Rank9SelIndex>::default().access_or(0, 3), 3);
+    }
+
+    #[test]
+    fn test_empty_slice() {
+        let seq = DacsByte::<Rank9SelIndex>::from_slice::<usize>(&[]).unwrap();
+        assert_eq!(seq, DacsByte::<Rank9SelIndex>::default());
+        let e = DacsByte::<Rank9SelIndex>::try_from_slice::<usize>(&[]);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("vals must not be empty.".to_string())
+        );
+        let seq = DacsByte::<Rank9SelIndex>::try_from_slice(&[1, 300]).unwrap();
+        assert_eq!(seq.to_vec(), vec![1, 300]);
+    }
+
+    #[test]
+    fn test_try_into_compact() {
+        for vals in [
+            vec![],
+            vec![0],
+            (0..=255).collect(),
+            (0..1000).map(|i| i % 7).collect(),
+        ] {
+            let seq = DacsByte::<Rank9SelIndex>::from_slice(&vals).unwrap();
+            let cv = seq.try_into_compact().unwrap();
+            assert_eq!(cv.width(), 8);
+            assert_eq!(cv.to_vec(), vals);
+        }
+        for vals in [vec![256], vec![0, 1, 1 << 20]] {
+            let seq = DacsByte::<Rank9SelIndex>::from_slice(&vals).unwrap();
+            assert_eq!(seq.try_into_compact(), None);
+        }
+    }
+
     #[test]
     fn test_all_zeros() {
         let seq = DacsByte::<Rank9SelIndex>::from_slice(&[0, 0, 0, 0]).unwrap();
@@ -542,12 +853,62 @@ mod tests {
         assert_eq!(collected, vec![5, 7]);
     }
 
+    #[test]
+    fn iter_matches_access() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        let vals: Vec<usize> = (0..500)
+            .map(|_| rng.gen::<usize>() >> rng.gen_range(0..64))
+            .collect();
+        let seq = DacsByte::<Rank9SelIndex>::from_slice(&vals).unwrap();
+        let expected: Vec<usize> = (0..seq.len()).map(|i| seq.access(i).unwrap()).collect();
+
+        let mut it = seq.iter();
+        assert_eq!(it.size_hint(), (seq.len(), Some(seq.len())));
+        it.next();
+        assert_eq!(it.size_hint(), (seq.len() - 1, Some(seq.len() - 1)));
+        assert_eq!(seq.iter().collect::<Vec<_>>(), expected);
+        assert_eq!(expected, vals);
+    }
+
     #[test]
     fn to_vec_collects() {
         let seq = DacsByte::<Rank9SelIndex>::from_slice(&[5, 7]).unwrap();
         assert_eq!(seq.to_vec(), vec![5, 7]);
     }
 
+    #[test]
+    fn enumerate_vals_matches_iter() {
+        let seq = DacsByte::<Rank9SelIndex>::from_slice(&[
+            0xFFFF, 0xFF, 0xF, 0xFFFFF, 0xF, 0x1FFFFFF, 0, 0x100,
+        ])
+        .unwrap();
+        let pairs: Vec<(usize, usize)> = seq.enumerate_vals().collect();
+        assert_eq!(pairs, seq.iter().enumerate().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_decode_range() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        let vals: Vec<usize> = (0..500)
+            .map(|_| rng.gen::<usize>() >> rng.gen_range(0..64))
+            .collect();
+        let seq = DacsByte::<Rank9SelIndex>::from_slice(&vals).unwrap();
+        let expected: Vec<usize> = (0..seq.len()).map(|i| seq.access(i).unwrap()).collect();
+
+        assert_eq!(seq.decode_range(0, seq.len()), Some(expected.clone()));
+        assert_eq!(seq.decode_range(seq.len(), seq.len()), Some(vec![]));
+        assert_eq!(seq.decode_range(0, seq.len() + 1), None);
+        assert_eq!(seq.decode_range(2, 1), None);
+
+        let mut buf = vec![];
+        for _ in 0..100 {
+            let lo = rng.gen_range(0..=seq.len());
+            let hi = rng.gen_range(lo..=seq.len());
+            seq.decode_range_into(lo, hi, &mut buf).unwrap();
+            assert_eq!(buf, expected[lo..hi]);
+        }
+    }
+
     #[test]
     fn bytes_roundtrip() {
         let seq = DacsByte::<Rank9SelIndex>::from_slice(&[5, 0, 100000, 334]).unwrap();
@@ -556,6 +917,48 @@ mod tests {
         assert_eq!(seq, other);
     }
 
+    #[test]
+    fn malformed_levels_access_none() {
+        let seq = DacsByte::<Rank9SelIndex>::from_slice(&[0xFFFF, 0xFF, 0x1FFFF]).unwrap();
+        let (mut meta, bytes) = seq.to_bytes();
+        assert_eq!(meta.level_lens, vec![3, 2, 1]);
+        meta.level_lens[1] = 1;
+        let other = DacsByte::<Rank9SelIndex>::from_bytes(meta, bytes).unwrap();
+        assert_eq!(other.access(0), Some(0xFFFF));
+        assert_eq!(other.access(1), Some(0xFF));
+        assert_eq!(other.access(2), None);
+    }
+
+    #[test]
+    fn malformed_flags_access_none() {
+        let seq = DacsByte::<Rank9SelIndex>::from_slice(&[0xFFFF, 0xFF, 0x1FFFF]).unwrap();
+        let mut b = BitVectorBuilder::new();
+        b.extend_bits([true]).unwrap();
+        let malformed = DacsByte {
+            bytes: seq.bytes.clone(),
+            data: seq.data.clone(),
+            flags: vec![b.freeze::<Rank9SelIndex>()],
+        };
+        assert_eq!(malformed.access(0), None);
+        assert_eq!(malformed.access(1), None);
+        assert_eq!(malformed.access(2), None);
+        let mut buf = vec![7];
+        assert_eq!(malformed.decode_range_into(2, 3, &mut buf), None);
+        assert_eq!(buf, vec![7]);
+    }
+
+    #[test]
+    fn from_bytes_short_flag_words() {
+        let seq = DacsByte::<Rank9SelIndex>::from_slice(&[0xFFFF, 0xFF]).unwrap();
+        let (mut meta, bytes) = seq.to_bytes();
+        meta.flag_meta[0].len_bits = 65;
+        let e = DacsByte::<Rank9SelIndex>::from_bytes(meta, bytes);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("bytes must hold at least len=65 bits, but got 64 bits.".to_string())
+        );
+    }
+
     #[test]
     fn test_from_slice_uncastable() {
         let e = DacsByte::<Rank9SelIndex>::from_slice(&[u128::MAX]);
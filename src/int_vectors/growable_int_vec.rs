@@ -0,0 +1,136 @@
+//! Append-only bit-packed integer vector that widens itself on demand.
+#![cfg(target_pointer_width = "64")]
+
+use crate::int_vectors::compact_vector::{CompactVector, CompactVectorBuilder};
+use crate::utils;
+
+/// Append-only integer vector that stays bit-packed without knowing the width up front.
+///
+/// The integers are collected in a [`CompactVectorBuilder`] of [`Self::width()`] bits,
+/// the number of bits needed for the largest value pushed so far. When a wider value
+/// arrives, all integers are re-packed into a builder of the new width. Since the width
+/// only grows up to 64, at most 63 re-packings happen over the lifetime of a vector.
+///
+/// # Examples
+///
+/// ```
+/// use jerky::int_vectors::GrowableIntVec;
+///
+/// let mut vec = GrowableIntVec::new();
+/// vec.push(3);
+/// vec.push(1000);
+/// assert_eq!(vec.width(), 10);
+/// assert_eq!(vec.get(0), Some(3));
+///
+/// let cv = vec.into_compact();
+/// assert_eq!(cv.to_vec(), vec![3, 1000]);
+/// assert_eq!(cv.width(), 10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GrowableIntVec {
+    builder: CompactVectorBuilder,
+}
+
+impl Default for GrowableIntVec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GrowableIntVec {
+    /// Creates a new empty vector.
+    pub fn new() -> Self {
+        Self {
+            builder: CompactVectorBuilder::new(1).unwrap(),
+        }
+    }
+
+    /// Pushes integer `val` at the end, re-packing all integers
+    /// if `val` does not fit in [`Self::width()`] bits.
+    pub fn push(&mut self, val: usize) {
+        let width = utils::needed_bits(val);
+        if self.width() < width {
+            self.rewiden(width);
+        }
+        self.builder.push_int(val).unwrap();
+    }
+
+    /// Returns the `pos`-th integer, or [`None`] if out of bounds.
+    pub fn get(&self, pos: usize) -> Option<usize> {
+        self.builder.get_int(pos)
+    }
+
+    /// Gets the number of integers.
+    pub const fn len(&self) -> usize {
+        self.builder.len()
+    }
+
+    /// Checks if the vector is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.builder.is_empty()
+    }
+
+    /// Returns the number of bits currently used to store each integer.
+    pub const fn width(&self) -> usize {
+        self.builder.width()
+    }
+
+    /// Converts the vector into a [`CompactVector`] of the current width
+    /// without copying the packed words.
+    pub fn into_compact(self) -> CompactVector {
+        self.builder.freeze()
+    }
+
+    /// Re-packs all integers in `width` bits.
+    fn rewiden(&mut self, width: usize) {
+        let builder = CompactVectorBuilder::with_capacity(self.len(), width).unwrap();
+        let old = core::mem::replace(&mut self.builder, builder).freeze();
+        self.builder.extend(old.iter()).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bit_vector::WORD_LEN;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    #[test]
+    fn test_empty() {
+        let vec = GrowableIntVec::new();
+        assert!(vec.is_empty());
+        assert_eq!(vec.get(0), None);
+        let cv = vec.into_compact();
+        assert!(cv.is_empty());
+        assert_eq!(cv.width(), 1);
+    }
+
+    #[test]
+    fn test_rewidening() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        let mut vec = GrowableIntVec::new();
+        let mut expected = vec![];
+        let mut widths = vec![];
+        for max_width in [1, 3, 7, 8, 13, 31, 33, 60, 64] {
+            for _ in 0..200 {
+                let val = rng.gen::<usize>() >> (WORD_LEN - rng.gen_range(1..=max_width));
+                vec.push(val);
+                expected.push(val);
+            }
+            vec.push(1 << (max_width - 1));
+            expected.push(1 << (max_width - 1));
+            widths.push(vec.width());
+            assert_eq!(vec.len(), expected.len());
+            for (pos, &val) in expected.iter().enumerate() {
+                assert_eq!(vec.get(pos), Some(val));
+            }
+        }
+        assert_eq!(widths, vec![1, 3, 7, 8, 13, 31, 33, 60, 64]);
+        assert_eq!(vec.get(expected.len()), None);
+
+        let cv = vec.into_compact();
+        assert_eq!(cv.width(), 64);
+        assert_eq!(cv.to_vec(), expected);
+    }
+}
@@ -21,6 +21,7 @@
 //! | --- | :-: | :-: | :-: |
 //! | [`CompactVector`] | $`O(1)`$ | $`O(1)`$  | $`n \lceil \lg u \rceil`$ |
 //! | [`DacsByte`] | $`O(\ell(a_i) / b)`$ | -- | $`\textrm{DAC}_\textrm{Byte}(A) + o(\textrm{DAC}_\textrm{Byte}(A)/b) + O(\lg u)`$ |
+//! | [`MonotoneVector`] | $`O(\lg n)`$ | -- | $`n (\lfloor \lg (u / n) \rfloor + 3) + o(n)`$ |
 //!
 //! The parameters are introduced below.
 //!
@@ -33,6 +34,21 @@
 //! This is the only updatable data structure and will be the fastest due to its simplicity.
 //! However, the compression performance is poor, especially when $`A`$ contains at least one large value.
 //!
+//! [`GrowableIntVec`] is an append-only companion of [`CompactVector`]
+//! that widens itself when a larger value is pushed,
+//! for streams whose maximum value is unknown in advance.
+//!
+//! ## Compressed format for sorted integers
+//!
+//! [`MonotoneVector`] stores a non-decreasing sequence of $`n`$ integers
+//! in the Elias-Fano encoding, which is much smaller than [`CompactVector`]
+//! when $`n`$ is large relative to $`u`$, and additionally answers rank queries over values.
+//!
+//! [`Permutation`] stores a permutation in a [`CompactVector`] with sampled
+//! back-pointers on its cycles, so its inverse is computed without a second array.
+//!
+//! [`RankIndexedMap`] composes a [`MonotoneVector`] of sparse keys with a [`CompactVector`]
+//! of their values, which are looked up at the rank of a key.
 //!
 //! ## Compressed format with Directly Addressable Codes
 //!
@@ -73,15 +89,25 @@
 //! ```
 pub mod compact_vector;
 pub mod dacs_byte;
+pub mod growable_int_vec;
+pub mod monotone_vector;
+pub mod permutation;
+pub mod rank_indexed_map;
 
 pub mod prelude;
 
 pub use compact_vector::{CompactVector, CompactVectorBuilder};
 pub use dacs_byte::DacsByte;
+pub use growable_int_vec::GrowableIntVec;
+pub use monotone_vector::MonotoneVector;
+pub use permutation::Permutation;
+pub use rank_indexed_map::RankIndexedMap;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use num_traits::ToPrimitive;
 
+use crate::utils;
+
 /// Interface for building integer vectors.
 pub trait Build {
     /// Creates a new vector from a slice of integers `vals`.
@@ -109,4 +135,369 @@ pub trait NumVals {
 pub trait Access {
     /// Returns the `pos`-th integer, or [`None`] if out of bounds.
     fn access(&self, pos: usize) -> Option<usize>;
+
+    /// Returns the `pos`-th integer as [`u64`], or [`None`] if out of bounds.
+    ///
+    /// The return type does not depend on the target pointer width,
+    /// so values of on-disk formats keep their logical width.
+    fn access_u64(&self, pos: usize) -> Option<u64> {
+        self.access(pos).map(|x| x as u64)
+    }
+}
+
+/// Interface for indexable integer sequences, combining [`NumVals`] and [`Access`].
+///
+/// It is implemented for every type implementing both traits,
+/// so generic code can take `&impl IntSequence`.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::int_vectors::{CompactVector, DacsByte, IntSequence};
+///
+/// fn sum(seq: &impl IntSequence) -> usize {
+///     IntSequence::iter(seq).sum()
+/// }
+///
+/// assert_eq!(sum(&CompactVector::from_slice(&[5, 0, 7])?), 12);
+/// assert_eq!(sum(&DacsByte::<jerky::bit_vector::Rank9SelIndex>::from_slice(&[5, 0, 7])?), 12);
+/// # Ok(())
+/// # }
+/// ```
+pub trait IntSequence: NumVals + Access {
+    /// Creates an iterator enumerating integers with [`Access::access`].
+    fn iter(&self) -> AccessIter<'_, Self> {
+        AccessIter { seq: self, pos: 0 }
+    }
+
+    /// Collects all integers into a `Vec<usize>`.
+    fn to_vec(&self) -> Vec<usize> {
+        IntSequence::iter(self).collect()
+    }
+}
+
+impl<S: NumVals + Access> IntSequence for S {}
+
+/// Iterator for enumerating integers, created by [`IntSequence::iter`].
+pub struct AccessIter<'a, S: ?Sized> {
+    seq: &'a S,
+    pos: usize,
+}
+
+impl<S: NumVals + Access + ?Sized> Iterator for AccessIter<'_, S> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.seq.access(self.pos)?;
+        self.pos += 1;
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.seq.num_vals() - self.pos;
+        (rest, Some(rest))
+    }
+}
+
+/// Creates an iterator over the gaps `seq[i + 1] - seq[i]` of a sequence
+/// assumed to be non-decreasing.
+///
+/// Each integer is fetched by [`Access::access`].
+/// Types with a faster sequential decoder provide their own `gaps` method,
+/// such as [`CompactVector::gaps`] and [`MonotoneVector::gaps`].
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::int_vectors::{gaps, DacsByte};
+///
+/// let seq: DacsByte = DacsByte::from_slice(&[1, 4, 4, 10])?;
+/// assert_eq!(gaps(&seq).collect::<Vec<_>>(), vec![3, 0, 6]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn gaps<S: IntSequence>(seq: &S) -> Gaps<AccessIter<'_, S>> {
+    Gaps::new(IntSequence::iter(seq))
+}
+
+/// Iterator adapter yielding the differences of consecutive integers,
+/// created by [`gaps`] and the `gaps` methods of integer vectors.
+///
+/// If an integer is smaller than its predecessor, the difference wraps around.
+pub struct Gaps<I> {
+    iter: I,
+    prev: Option<usize>,
+}
+
+impl<I: Iterator<Item = usize>> Gaps<I> {
+    /// Creates a new adapter over the integers of `iter`.
+    pub const fn new(iter: I) -> Self {
+        Self { iter, prev: None }
+    }
+}
+
+impl<I: Iterator<Item = usize>> Iterator for Gaps<I> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let prev = match self.prev {
+            Some(prev) => prev,
+            None => self.iter.next()?,
+        };
+        let x = self.iter.next()?;
+        self.prev = Some(x);
+        Some(x.wrapping_sub(prev))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iter.size_hint();
+        if self.prev.is_some() {
+            (lo, hi)
+        } else {
+            (lo.saturating_sub(1), hi.map(|hi| hi.saturating_sub(1)))
+        }
+    }
+}
+
+/// Creates an iterator merging sorted integer sequences into one sorted stream,
+/// e.g., to union posting lists without materializing them.
+///
+/// Each sequence must be non-decreasing on its own; otherwise the output is
+/// not sorted. Duplicates are kept, and equal integers are yielded in the order
+/// of their sequences in `seqs`. A binary heap holds the next integer of each
+/// sequence, so each step takes $`O(\lg k)`$ time for $`k`$ sequences.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::int_vectors::{kmerge, CompactVector, MonotoneVector};
+///
+/// let a = CompactVector::from_slice(&[1, 4, 9])?;
+/// let b = MonotoneVector::from_slice(&[2, 4, 5])?;
+/// let merged: Vec<usize> = kmerge(&[&a, &b]).collect();
+/// assert_eq!(merged, vec![1, 2, 4, 4, 5, 9]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn kmerge<'a>(seqs: &[&'a dyn Access]) -> KMerge<'a> {
+    let mut heap = std::collections::BinaryHeap::with_capacity(seqs.len());
+    for (src, seq) in seqs.iter().enumerate() {
+        if let Some(x) = seq.access(0) {
+            heap.push(core::cmp::Reverse((x, src)));
+        }
+    }
+    KMerge {
+        seqs: seqs.to_vec(),
+        positions: vec![0; seqs.len()],
+        heap,
+    }
+}
+
+/// Iterator merging sorted integer sequences, created by [`kmerge`].
+pub struct KMerge<'a> {
+    seqs: Vec<&'a dyn Access>,
+    positions: Vec<usize>,
+    /// Next integer of each unfinished sequence, with the sequence index.
+    heap: std::collections::BinaryHeap<core::cmp::Reverse<(usize, usize)>>,
+}
+
+impl Iterator for KMerge<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let core::cmp::Reverse((x, src)) = self.heap.pop()?;
+        self.positions[src] += 1;
+        if let Some(y) = self.seqs[src].access(self.positions[src]) {
+            self.heap.push(core::cmp::Reverse((y, src)));
+        }
+        Some(x)
+    }
+}
+
+/// Counts the integers in `vals` by the number of bits needed to represent them.
+///
+/// The `i`-th bin holds the number of integers `x` with
+/// [`utils::needed_bits(x)`](utils::needed_bits) equal to `i`.
+/// Since zero is stored in one bit, the 0-th bin is always zero.
+/// The histogram helps choose between [`CompactVector`] and [`DacsByte`].
+///
+/// # Arguments
+///
+///  - `vals`: Slice of integers to be examined.
+///
+/// # Errors
+///
+/// An error is returned if `vals` contains an integer that cannot be cast to [`usize`].
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::int_vectors::bit_length_histogram;
+///
+/// let hist = bit_length_histogram(&[0, 1, 5, 7, 256])?;
+/// assert_eq!(hist[1], 2);
+/// assert_eq!(hist[3], 2);
+/// assert_eq!(hist[9], 1);
+/// assert_eq!(hist.iter().sum::<usize>(), 5);
+/// # Ok(())
+/// # }
+/// ```
+pub fn bit_length_histogram<T>(vals: &[T]) -> Result<[usize; 65]>
+where
+    T: ToPrimitive,
+{
+    let mut hist = [0; 65];
+    for x in vals {
+        let x = x
+            .to_usize()
+            .ok_or_else(|| anyhow!("vals must consist only of values castable into usize."))?;
+        hist[utils::needed_bits(x)] += 1;
+    }
+    Ok(hist)
+}
+
+/// Computes the width [`CompactVector::from_slice`] would choose for `vals`,
+/// i.e., the number of bits needed to represent the largest integer,
+/// without building the vector.
+///
+/// An empty `vals` gives 1, the width of [`CompactVector::default()`].
+///
+/// # Arguments
+///
+///  - `vals`: Slice of integers to be examined.
+///
+/// # Errors
+///
+/// An error is returned if `vals` contains an integer that cannot be cast to [`usize`].
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::int_vectors::{minimal_width, CompactVector};
+///
+/// assert_eq!(minimal_width(&[7, 2])?, 3);
+/// assert_eq!(minimal_width(&[7, 2])?, CompactVector::from_slice(&[7, 2])?.width());
+/// assert_eq!(minimal_width::<u32>(&[])?, 1);
+/// # Ok(())
+/// # }
+/// ```
+pub fn minimal_width<T>(vals: &[T]) -> Result<usize>
+where
+    T: ToPrimitive,
+{
+    let mut max_int = 0;
+    for x in vals {
+        let x = x
+            .to_usize()
+            .ok_or_else(|| anyhow!("vals must consist only of values castable into usize."))?;
+        max_int = max_int.max(x);
+    }
+    Ok(utils::needed_bits(max_int))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bit_vector::Rank9SelIndex;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    fn check_int_sequence<S: IntSequence>(seq: &S, vals: &[usize]) {
+        assert_eq!(seq.num_vals(), vals.len());
+        let mut it = IntSequence::iter(seq);
+        assert_eq!(it.size_hint(), (vals.len(), Some(vals.len())));
+        assert_eq!(it.next(), vals.first().copied());
+        assert_eq!(IntSequence::to_vec(seq), vals);
+    }
+
+    #[test]
+    fn test_int_sequence() {
+        let vals = [5, 0, 100000, 334, 1];
+        check_int_sequence(&CompactVector::from_slice(&vals).unwrap(), &vals);
+        check_int_sequence(
+            &DacsByte::<Rank9SelIndex>::from_slice(&vals).unwrap(),
+            &vals,
+        );
+        check_int_sequence(&CompactVector::default(), &[]);
+    }
+
+    #[test]
+    fn test_minimal_width() {
+        assert_eq!(minimal_width::<usize>(&[]).unwrap(), 1);
+        assert_eq!(minimal_width(&[0u8, 0, 0]).unwrap(), 1);
+        assert_eq!(minimal_width(&[3, 0, 255, 17]).unwrap(), 8);
+        assert_eq!(minimal_width(&[usize::MAX]).unwrap(), 64);
+        for vals in [vec![0usize, 0], vec![3, 0, 256, 17], vec![1 << 40]] {
+            assert_eq!(
+                minimal_width(&vals).unwrap(),
+                CompactVector::from_slice(&vals).unwrap().width()
+            );
+        }
+        assert!(minimal_width(&[-1i32]).is_err());
+    }
+
+    #[test]
+    fn test_access_u64() {
+        let vals = [u32::MAX as usize + 1, 0, 1 << 40, usize::MAX];
+        let cv = CompactVector::from_slice(&vals).unwrap();
+        let seq = DacsByte::<Rank9SelIndex>::from_slice(&vals).unwrap();
+        for (pos, &val) in vals.iter().enumerate() {
+            assert_eq!(cv.access_u64(pos), Some(val as u64));
+            assert_eq!(seq.access_u64(pos), Some(val as u64));
+        }
+        assert_eq!(cv.access_u64(vals.len()), None);
+        assert_eq!(seq.access_u64(vals.len()), None);
+    }
+
+    #[test]
+    fn test_kmerge() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        let mut gen = |n: usize| {
+            let mut vals: Vec<usize> = (0..n).map(|_| rng.gen_range(0..100)).collect();
+            vals.sort_unstable();
+            vals
+        };
+        let (a, b, c) = (gen(50), gen(0), gen(80));
+        let cv_a = CompactVector::from_slice(&a).unwrap();
+        let cv_b = CompactVector::from_slice(&b).unwrap();
+        let mv_c = MonotoneVector::from_slice(&c).unwrap();
+
+        let mut expected = [a.clone(), c.clone()].concat();
+        expected.sort_unstable();
+        assert_eq!(kmerge(&[&cv_a, &mv_c]).collect::<Vec<_>>(), expected);
+
+        let mut expected = [a, b, c].concat();
+        expected.sort_unstable();
+        assert_eq!(kmerge(&[&cv_a, &cv_b, &mv_c]).collect::<Vec<_>>(), expected);
+        assert_eq!(kmerge(&[]).next(), None);
+    }
+
+    #[test]
+    fn test_bit_length_histogram() {
+        let hist = bit_length_histogram(&[0u64, 1, 2, 3, 4, 255, 256, u64::MAX]).unwrap();
+        let mut expected = [0; 65];
+        expected[1] = 2;
+        expected[2] = 2;
+        expected[3] = 1;
+        expected[8] = 1;
+        expected[9] = 1;
+        expected[64] = 1;
+        assert_eq!(hist, expected);
+        assert_eq!(bit_length_histogram::<u32>(&[]).unwrap(), [0; 65]);
+    }
+
+    #[test]
+    fn test_bit_length_histogram_uncastable() {
+        let e = bit_length_histogram(&[-1i32]);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("vals must consist only of values castable into usize.".to_string())
+        );
+    }
 }
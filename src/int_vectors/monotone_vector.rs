@@ -0,0 +1,710 @@
+//! Elias-Fano encoded vector of non-decreasing integers.
+#![cfg(target_pointer_width = "64")]
+
+use core::iter::Peekable;
+
+use anyhow::{anyhow, Result};
+use num_traits::ToPrimitive;
+
+use crate::bit_vector::{
+    self, BitVector, BitVectorBuilder, BitVectorData, NoIndex, Ones, Rank9SelIndex, Select,
+    ToDense, WORD_LEN,
+};
+use crate::int_vectors::{Access, Build, Gaps, NumVals};
+
+/// Compressed vector of non-decreasing integers in the Elias-Fano encoding.
+///
+/// Each integer is split into its lowest `low_len` bits, stored verbatim,
+/// and the remaining high bits, stored in unary in a bit vector
+/// with [`Rank9SelIndex`]. For $`n`$ integers with the largest value $`u - 1`$,
+/// the vector takes at most $`n (\lfloor \lg (u / n) \rfloor + 3) + o(n)`$ bits
+/// instead of $`n \lceil \lg u \rceil`$ bits of [`CompactVector`](super::CompactVector).
+///
+/// Prefer it over [`CompactVector`](super::CompactVector) when the input is sorted,
+/// e.g., positions or offsets, and a logarithmic-time access is acceptable.
+/// The encoding also supports [`Self::rank`] over the value space.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::int_vectors::{Access, MonotoneVector};
+///
+/// let seq = MonotoneVector::from_slice(&[1, 3, 3, 7, 12])?;
+///
+/// assert_eq!(seq.len(), 5);
+/// assert_eq!(seq.access(3), Some(7));
+/// assert_eq!(seq.access(5), None);
+/// assert_eq!(seq.rank(3), 1);
+/// assert_eq!(seq.rank(4), 3);
+///
+/// assert!(MonotoneVector::from_slice(&[2, 1]).is_err());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # References
+///
+/// - P. Elias, "Efficient storage and retrieval by content and address of static files."
+///   Journal of the ACM, 21(2), 246-260, 1974.
+/// - R. M. Fano, "On the number of bits required to implement an associative memory."
+///   Memorandum 61, Computer Structures Group, MIT, 1971.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonotoneVector {
+    high_bits: BitVector<Rank9SelIndex>,
+    low_bits: BitVector<NoIndex>,
+    low_len: usize,
+    len: usize,
+}
+
+impl Default for MonotoneVector {
+    fn default() -> Self {
+        Self::from_slice::<usize>(&[]).unwrap()
+    }
+}
+
+impl MonotoneVector {
+    /// Creates a new vector from a slice of non-decreasing integers `vals`.
+    ///
+    /// An empty `vals` gives an empty vector equal to [`Self::default()`];
+    /// use [`Self::try_from_slice`] to reject it instead.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if
+    ///
+    ///  - `vals` contains an integer that cannot be cast to [`usize`], or
+    ///  - `vals` is not non-decreasing.
+    pub fn from_slice<T>(vals: &[T]) -> Result<Self>
+    where
+        T: ToPrimitive,
+    {
+        let mut prev = 0;
+        for (i, x) in vals.iter().enumerate() {
+            let x = x
+                .to_usize()
+                .ok_or_else(|| anyhow!("vals must consist only of values castable into usize."))?;
+            if x < prev {
+                return Err(anyhow!(
+                    "vals must be non-decreasing, but got {x} at position {i} after {prev}."
+                ));
+            }
+            prev = x;
+        }
+
+        Ok(Self::encode(
+            vals.len(),
+            prev,
+            vals.iter().map(|x| x.to_usize().unwrap()),
+        ))
+    }
+
+    /// Creates a new vector from a non-empty slice of non-decreasing integers `vals`
+    /// in the same way as [`Self::from_slice`].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `vals` is empty or [`Self::from_slice`] fails.
+    pub fn try_from_slice<T>(vals: &[T]) -> Result<Self>
+    where
+        T: ToPrimitive,
+    {
+        if vals.is_empty() {
+            return Err(anyhow!("vals must not be empty."));
+        }
+        Self::from_slice(vals)
+    }
+
+    /// Encodes `len` non-decreasing integers `vals` whose largest is `last`.
+    fn encode<I>(len: usize, last: usize, vals: I) -> Self
+    where
+        I: Iterator<Item = usize>,
+    {
+        let universe = last.saturating_add(1);
+        let low_len = if len != 0 && len < universe {
+            WORD_LEN - 1 - (universe / len).leading_zeros() as usize
+        } else {
+            0
+        };
+
+        let mut high_bits = BitVectorBuilder::from_bit(false, (last >> low_len) + len + 1);
+        let mut low_bits = BitVectorBuilder::with_capacity(len * low_len);
+        for (i, x) in vals.enumerate() {
+            high_bits.set_bit((x >> low_len) + i, true).unwrap();
+            low_bits.push_bits(x, low_len).unwrap();
+        }
+
+        Self {
+            high_bits: high_bits.freeze::<Rank9SelIndex>(),
+            low_bits: low_bits.freeze::<NoIndex>(),
+            low_len,
+            len,
+        }
+    }
+
+    /// Creates the sorted union of the integers in `self` and `other`.
+    ///
+    /// Both vectors are treated as sets, so every distinct integer appears once
+    /// in the result, even if it is repeated within or across the inputs.
+    /// The inputs are merged through [`Self::iter`] twice, once to size the result
+    /// and once to encode it, without decoding them into temporary vectors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::MonotoneVector;
+    ///
+    /// let a = MonotoneVector::from_slice(&[1, 3, 3, 7])?;
+    /// let b = MonotoneVector::from_slice(&[2, 3, 9])?;
+    /// let c = a.union(&b);
+    /// assert_eq!(c.iter().collect::<Vec<_>>(), vec![1, 2, 3, 7, 9]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        let mut len = 0;
+        let mut last = 0;
+        for x in Union::new(self.iter(), other.iter()) {
+            len += 1;
+            last = x;
+        }
+        Self::encode(len, last, Union::new(self.iter(), other.iter()))
+    }
+
+    /// Gets the number of integers.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the vector is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets the number of lower bits stored verbatim per integer.
+    pub const fn low_len(&self) -> usize {
+        self.low_len
+    }
+
+    /// Gets the total number of bits of the high and low parts,
+    /// excluding the index on the high part.
+    pub fn num_bits_total(&self) -> usize {
+        self.high_bits.len() + self.low_bits.len()
+    }
+
+    /// Creates an iterator for enumerating integers.
+    ///
+    /// The high part is scanned sequentially instead of answering
+    /// a select query per integer, so enumerating all integers takes
+    /// time linear in the number of bits stored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::MonotoneVector;
+    ///
+    /// let seq = MonotoneVector::from_slice(&[1, 3, 3, 7])?;
+    /// assert_eq!(seq.iter().collect::<Vec<_>>(), vec![1, 3, 3, 7]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            seq: self,
+            ones: self.high_bits.data.ones(),
+            pos: 0,
+        }
+    }
+
+    /// Creates an iterator over the gaps `self[i + 1] - self[i]`.
+    ///
+    /// The integers are decoded by [`Self::iter`], so enumerating all gaps
+    /// takes linear time rather than a select query per gap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::MonotoneVector;
+    ///
+    /// let seq = MonotoneVector::from_slice(&[1, 3, 3, 7])?;
+    /// assert_eq!(seq.gaps().collect::<Vec<_>>(), vec![2, 0, 4]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn gaps(&self) -> Gaps<Iter<'_>> {
+        Gaps::new(self.iter())
+    }
+
+    /// Returns the number of integers less than `val`.
+    ///
+    /// # Complexity
+    ///
+    /// $`O(\lg u + u / n)`$ in the worst case, where the second term is
+    /// the number of integers sharing the high bits of `val`.
+    pub fn rank(&self, val: usize) -> usize {
+        let high = val >> self.low_len;
+        let low = self.low(val);
+        // Position in `high_bits` where the bucket of `high` starts.
+        let mut hpos = if high == 0 {
+            0
+        } else {
+            match self.high_bits.select0(high - 1) {
+                Some(p) => p + 1,
+                None => return self.len,
+            }
+        };
+        let mut rank = hpos - high;
+        while rank < self.len
+            && bit_vector::Access::access(&self.high_bits, hpos) == Some(true)
+            && self.low_at(rank) < low
+        {
+            hpos += 1;
+            rank += 1;
+        }
+        rank
+    }
+
+    /// Returns the smallest integer no less than `val`,
+    /// or [`None`] if every integer is less than `val`.
+    ///
+    /// # Complexity
+    ///
+    /// Same as [`Self::rank`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::MonotoneVector;
+    ///
+    /// let seq = MonotoneVector::from_slice(&[1, 3, 7])?;
+    /// assert_eq!(seq.successor(3), Some(3));
+    /// assert_eq!(seq.successor(4), Some(7));
+    /// assert_eq!(seq.successor(8), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn successor(&self, val: usize) -> Option<usize> {
+        self.access(self.rank(val))
+    }
+
+    /// Creates an iterator over the distinct integers contained in both `self` and `other`,
+    /// in increasing order.
+    ///
+    /// Like [`Self::union`], both vectors are treated as sets.
+    /// If one vector has fewer than `1/32` of the integers of the other,
+    /// each integer of the smaller one is probed in the larger one with
+    /// [`Self::successor`]; otherwise, both are merged through [`Self::iter`].
+    ///
+    /// # Complexity
+    ///
+    /// $`O(m (\lg u + u / n))`$ when probing, where $`m`$ is the length of the smaller vector
+    /// and $`n`$ and $`u`$ are those of the larger one, and $`O(m + n)`$ when merging.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use jerky::int_vectors::MonotoneVector;
+    ///
+    /// let a = MonotoneVector::from_slice(&[1, 3, 3, 7, 9])?;
+    /// let b = MonotoneVector::from_slice(&[2, 3, 9])?;
+    /// assert_eq!(a.intersection(&b).collect::<Vec<_>>(), vec![3, 9]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn intersection<'a>(&'a self, other: &'a Self) -> Intersection<'a> {
+        let (small, large) = if self.len <= other.len {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let strategy = if small.len.saturating_mul(INTERSECTION_PROBE_RATIO) < large.len {
+            IntersectionStrategy::Probe {
+                small: small.iter(),
+                large,
+            }
+        } else {
+            IntersectionStrategy::Merge {
+                a: small.iter().peekable(),
+                b: large.iter().peekable(),
+            }
+        };
+        Intersection {
+            strategy,
+            prev: None,
+        }
+    }
+
+    #[inline(always)]
+    fn low(&self, val: usize) -> usize {
+        if self.low_len == 0 {
+            0
+        } else {
+            val & (usize::MAX >> (WORD_LEN - self.low_len))
+        }
+    }
+
+    #[inline(always)]
+    fn low_at(&self, pos: usize) -> usize {
+        self.low_bits
+            .get_bits(pos * self.low_len, self.low_len)
+            .unwrap()
+    }
+}
+
+impl Build for MonotoneVector {
+    /// Creates a new vector from a slice of integers `vals`.
+    ///
+    /// This just calls [`Self::from_slice()`]. See the documentation.
+    fn build_from_slice<T>(vals: &[T]) -> Result<Self>
+    where
+        T: ToPrimitive,
+        Self: Sized,
+    {
+        Self::from_slice(vals)
+    }
+}
+
+impl NumVals for MonotoneVector {
+    /// Returns the number of integers stored (just wrapping [`Self::len()`]).
+    fn num_vals(&self) -> usize {
+        self.len()
+    }
+}
+
+impl Access for MonotoneVector {
+    /// Returns the `pos`-th integer, or [`None`] if out of bounds.
+    ///
+    /// # Complexity
+    ///
+    /// $`O(\lg n)`$ for the select query on the high part.
+    fn access(&self, pos: usize) -> Option<usize> {
+        if self.len <= pos {
+            return None;
+        }
+        let high = self.high_bits.select1(pos)? - pos;
+        Some((high << self.low_len) | self.low_at(pos))
+    }
+}
+
+/// Sets the bit at every integer in a bit vector of length one past the largest integer,
+/// so repeated integers are represented by a single bit.
+impl ToDense for MonotoneVector {
+    fn to_dense(&self) -> BitVectorData {
+        let universe = self
+            .len()
+            .checked_sub(1)
+            .and_then(|pos| self.access(pos))
+            .map_or(0, |x| x + 1);
+        let mut builder = BitVectorBuilder::from_bit(false, universe);
+        for x in self.iter() {
+            builder.set_bit(x, true).unwrap();
+        }
+        builder.into_data()
+    }
+}
+
+/// Iterator for enumerating integers, created by [`MonotoneVector::iter()`].
+pub struct Iter<'a> {
+    seq: &'a MonotoneVector,
+    ones: Ones<'a>,
+    pos: usize,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let high = self.ones.next()? - self.pos;
+        let x = (high << self.seq.low_len) | self.seq.low_at(self.pos);
+        self.pos += 1;
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.seq.len - self.pos;
+        (rest, Some(rest))
+    }
+}
+
+/// Iterator merging two sorted iterators into their distinct integers,
+/// created in [`MonotoneVector::union()`].
+struct Union<'a> {
+    a: Peekable<Iter<'a>>,
+    b: Peekable<Iter<'a>>,
+    prev: Option<usize>,
+}
+
+impl<'a> Union<'a> {
+    fn new(a: Iter<'a>, b: Iter<'a>) -> Self {
+        Self {
+            a: a.peekable(),
+            b: b.peekable(),
+            prev: None,
+        }
+    }
+}
+
+impl Iterator for Union<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let x = match (self.a.peek(), self.b.peek()) {
+                (Some(&x), Some(&y)) if x <= y => self.a.next(),
+                (Some(_), Some(_)) | (None, Some(_)) => self.b.next(),
+                (Some(_), None) => self.a.next(),
+                (None, None) => return None,
+            }?;
+            if self.prev != Some(x) {
+                self.prev = Some(x);
+                return Some(x);
+            }
+        }
+    }
+}
+
+/// Size ratio above which [`MonotoneVector::intersection()`] probes the larger vector.
+const INTERSECTION_PROBE_RATIO: usize = 32;
+
+/// Iterator over the distinct integers common to two vectors,
+/// created by [`MonotoneVector::intersection()`].
+pub struct Intersection<'a> {
+    strategy: IntersectionStrategy<'a>,
+    prev: Option<usize>,
+}
+
+enum IntersectionStrategy<'a> {
+    Merge {
+        a: Peekable<Iter<'a>>,
+        b: Peekable<Iter<'a>>,
+    },
+    Probe {
+        small: Iter<'a>,
+        large: &'a MonotoneVector,
+    },
+}
+
+impl Iterator for Intersection<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let x = match &mut self.strategy {
+                IntersectionStrategy::Merge { a, b } => {
+                    let (&x, &y) = (a.peek()?, b.peek()?);
+                    if x < y {
+                        a.next();
+                        continue;
+                    }
+                    if y < x {
+                        b.next();
+                        continue;
+                    }
+                    a.next();
+                    b.next();
+                    x
+                }
+                IntersectionStrategy::Probe { small, large } => {
+                    let x = small.next()?;
+                    if self.prev == Some(x) || large.successor(x) != Some(x) {
+                        continue;
+                    }
+                    x
+                }
+            };
+            if self.prev != Some(x) {
+                self.prev = Some(x);
+                return Some(x);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::int_vectors::CompactVector;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    fn gen_sorted(len: usize, max: usize, seed: u64) -> Vec<usize> {
+        let mut rng = ChaChaRng::seed_from_u64(seed);
+        let mut vals: Vec<usize> = (0..len).map(|_| rng.gen_range(0..=max)).collect();
+        vals.sort_unstable();
+        vals
+    }
+
+    #[test]
+    fn test_access_and_rank() {
+        for (len, max) in [
+            (0, 0),
+            (1, 0),
+            (10, 0),
+            (100, 50),
+            (100, 10000),
+            (1000, 1 << 40),
+        ] {
+            let vals = gen_sorted(len, max, 334);
+            let seq = MonotoneVector::from_slice(&vals).unwrap();
+            assert_eq!(seq.len(), len);
+            for (i, &x) in vals.iter().enumerate() {
+                assert_eq!(seq.access(i), Some(x));
+            }
+            assert_eq!(seq.access(len), None);
+
+            let mut rng = ChaChaRng::seed_from_u64(114514);
+            let mut queries: Vec<usize> = (0..100).map(|_| rng.gen_range(0..=max + 1)).collect();
+            queries.extend(vals.iter().copied());
+            queries.push(usize::MAX);
+            for q in queries {
+                let expected = vals.iter().filter(|&&x| x < q).count();
+                assert_eq!(seq.rank(q), expected, "q={q}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_iter_and_gaps() {
+        for (len, max) in [(0, 0), (1, 5), (100, 0), (1000, 1 << 20)] {
+            let vals = gen_sorted(len, max, 334);
+            let seq = MonotoneVector::from_slice(&vals).unwrap();
+            assert_eq!(seq.iter().collect::<Vec<_>>(), vals);
+            let expected: Vec<usize> = vals.windows(2).map(|w| w[1] - w[0]).collect();
+            assert_eq!(seq.gaps().collect::<Vec<_>>(), expected);
+            let cv = CompactVector::from_slice(&vals).unwrap();
+            assert_eq!(cv.gaps().collect::<Vec<_>>(), expected);
+            assert_eq!(crate::int_vectors::gaps(&seq).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_constant_gaps() {
+        let seq = MonotoneVector::from_slice(&[7; 50]).unwrap();
+        let gaps = seq.gaps();
+        assert_eq!(gaps.size_hint(), (49, Some(49)));
+        assert_eq!(gaps.collect::<Vec<_>>(), vec![0; 49]);
+    }
+
+    #[test]
+    fn test_max_value() {
+        let vals = [0, usize::MAX - 1, usize::MAX];
+        let seq = MonotoneVector::from_slice(&vals).unwrap();
+        assert_eq!(seq.access(1), Some(usize::MAX - 1));
+        assert_eq!(seq.access(2), Some(usize::MAX));
+        assert_eq!(seq.rank(usize::MAX), 2);
+    }
+
+    #[test]
+    fn test_smaller_than_compact_vector() {
+        let vals = gen_sorted(10000, 1 << 30, 334);
+        let seq = MonotoneVector::from_slice(&vals).unwrap();
+        let cv = CompactVector::from_slice(&vals).unwrap();
+        assert_eq!(seq.low_len(), 16);
+        assert!(seq.num_bits_total() <= vals.len() * (seq.low_len() + 3) + 1);
+        assert!(seq.num_bits_total() < cv.len() * cv.width() * 2 / 3);
+    }
+
+    #[test]
+    fn test_decreasing() {
+        let e = MonotoneVector::from_slice(&[1, 5, 4]);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("vals must be non-decreasing, but got 4 at position 2 after 5.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_union() {
+        let sets = |x: &[usize], y: &[usize]| {
+            let mut expected = [x, y].concat();
+            expected.sort_unstable();
+            expected.dedup();
+            let a = MonotoneVector::from_slice(x).unwrap();
+            let b = MonotoneVector::from_slice(y).unwrap();
+            let c = a.union(&b);
+            assert_eq!(c.iter().collect::<Vec<_>>(), expected);
+            assert_eq!(c, MonotoneVector::from_slice(&expected).unwrap());
+            assert_eq!(b.union(&a), c);
+        };
+        sets(&[], &[]);
+        sets(&[0], &[]);
+        sets(&[1, 2, 3], &[10, 20, 30]);
+        sets(&[1, 3, 3, 5], &[0, 3, 5, 5, 6]);
+        sets(&[4, 4, 4], &[4]);
+        sets(&[0, usize::MAX], &[usize::MAX - 1]);
+        sets(
+            &gen_sorted(1000, 5000, 334),
+            &gen_sorted(700, 1 << 20, 114514),
+        );
+    }
+
+    #[test]
+    fn test_intersection() {
+        let sets = |x: &[usize], y: &[usize]| {
+            let mut expected: Vec<usize> = x.iter().copied().filter(|v| y.contains(v)).collect();
+            expected.dedup();
+            let a = MonotoneVector::from_slice(x).unwrap();
+            let b = MonotoneVector::from_slice(y).unwrap();
+            assert_eq!(a.intersection(&b).collect::<Vec<_>>(), expected);
+            assert_eq!(b.intersection(&a).collect::<Vec<_>>(), expected);
+        };
+        sets(&[], &[]);
+        sets(&[1, 2], &[]);
+        sets(&[1, 2, 3], &[10, 20, 30]);
+        sets(&[1, 3, 3, 5], &[0, 3, 5, 5, 6]);
+        sets(&[4, 4, 4], &[4]);
+        sets(&[0, usize::MAX], &[0, usize::MAX - 1, usize::MAX]);
+        let vals = gen_sorted(1000, 5000, 334);
+        sets(&vals, &vals);
+        sets(&vals, &gen_sorted(700, 5000, 114514));
+        // Probing the larger vector.
+        sets(&gen_sorted(10, 5000, 114514), &vals);
+        sets(&[5001, 6000], &vals);
+        sets(&[vals[3], vals[3], vals[500], 9999], &vals);
+    }
+
+    #[test]
+    fn test_to_dense() {
+        let vals = gen_sorted(1000, 1 << 14, 334);
+        let seq = MonotoneVector::from_slice(&vals).unwrap();
+        let dense = seq.to_dense();
+        assert_eq!(dense.len(), vals[vals.len() - 1] + 1);
+        assert_eq!(
+            dense,
+            BitVectorData::from_bits((0..dense.len()).map(|i| vals.binary_search(&i).is_ok()))
+        );
+
+        let seq = MonotoneVector::from_slice(&[1, 4, 6]).unwrap();
+        assert_eq!(seq.to_dense().to_bit_string(), "0100101");
+        assert_eq!(seq.to_dense().ones().count(), seq.len());
+        let seq = MonotoneVector::from_slice(&[2, 2]).unwrap();
+        assert_eq!(seq.to_dense().to_bit_string(), "001");
+        assert!(MonotoneVector::default().to_dense().is_empty());
+    }
+
+    #[test]
+    fn test_empty_slice() {
+        let seq = MonotoneVector::from_slice::<usize>(&[]).unwrap();
+        assert_eq!(seq, MonotoneVector::default());
+        let e = MonotoneVector::try_from_slice::<usize>(&[]);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("vals must not be empty.".to_string())
+        );
+        assert!(MonotoneVector::try_from_slice(&[2, 1]).is_err());
+        assert_eq!(MonotoneVector::try_from_slice(&[1, 2]).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_default() {
+        let seq = MonotoneVector::default();
+        assert!(seq.is_empty());
+        assert_eq!(seq.access(0), None);
+        assert_eq!(seq.rank(10), 0);
+    }
+}
@@ -0,0 +1,232 @@
+//! Permutation supporting access to its inverse via cycle shortcuts.
+#![cfg(target_pointer_width = "64")]
+
+use anyhow::{anyhow, Result};
+
+use crate::bit_vector::{Access as _, BitVector, BitVectorBuilder, Rank, Rank9SelIndex};
+use crate::int_vectors::CompactVector;
+use crate::utils;
+
+/// Permutation $`\pi`$ of $`0..n`$ supporting both $`\pi(i)`$ and $`\pi^{-1}(j)`$,
+/// based on the shortcut technique of Munro et al.
+///
+/// $`\pi`$ is stored in a [`CompactVector`]. On each cycle longer than the step $`t`$,
+/// every $`t`$-th element is marked in a bit vector with [`Rank9SelIndex`]
+/// and given a back-pointer to the element $`t`$ steps before it on the cycle.
+/// $`\pi^{-1}(j)`$ follows the cycle of $`j`$ forward until a marked element,
+/// takes its back-pointer, and follows the cycle again up to the predecessor of $`j`$,
+/// so it evaluates $`\pi`$ at most $`2t`$ times.
+///
+/// The structure takes $`n \lceil \lg n \rceil (1 + 1/t) + n + o(n)`$ bits,
+/// compared with $`2 n \lceil \lg n \rceil`$ bits for storing both arrays.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::int_vectors::Permutation;
+///
+/// let perm = Permutation::from_slice(&[2, 0, 3, 1])?;
+///
+/// assert_eq!(perm.len(), 4);
+/// assert_eq!(perm.apply(0), Some(2));
+/// assert_eq!(perm.inverse(2), Some(0));
+/// assert_eq!(perm.inverse(1), Some(3));
+/// assert_eq!(perm.inverse(4), None);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permutation {
+    perm: CompactVector,
+    marks: BitVector<Rank9SelIndex>,
+    back: CompactVector,
+    step: usize,
+}
+
+impl Permutation {
+    /// Default step between marked elements on a cycle.
+    pub const DEFAULT_STEP: usize = 32;
+
+    /// Creates a new permutation from `vals` with [`Self::DEFAULT_STEP`].
+    ///
+    /// # Arguments
+    ///
+    ///  - `vals`: Images $`\pi(0), \dots, \pi(n-1)`$.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `vals` is not a permutation of `0..vals.len()`.
+    pub fn from_slice(vals: &[usize]) -> Result<Self> {
+        Self::from_slice_with_step(vals, Self::DEFAULT_STEP)
+    }
+
+    /// Creates a new permutation from `vals`,
+    /// marking every `step`-th element on cycles longer than `step`.
+    ///
+    /// A smaller `step` speeds up [`Self::inverse`] at the cost of
+    /// $`n / \mathit{step}`$ back-pointers.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `vals` is not a permutation of `0..vals.len()`
+    /// or `step` is zero.
+    pub fn from_slice_with_step(vals: &[usize], step: usize) -> Result<Self> {
+        if step == 0 {
+            return Err(anyhow!("step must be at least 1, but got 0."));
+        }
+        let n = vals.len();
+        let mut seen = vec![false; n];
+        for &x in vals {
+            if n <= x || seen[x] {
+                return Err(anyhow!(
+                    "vals must be a permutation of 0..{n}, but got {x} out of range or repeated."
+                ));
+            }
+            seen[x] = true;
+        }
+
+        let mut marks = BitVectorBuilder::from_bit(false, n);
+        let mut back = vec![];
+        let mut visited = vec![false; n];
+        let mut cycle = vec![];
+        for start in 0..n {
+            if visited[start] {
+                continue;
+            }
+            cycle.clear();
+            let mut x = start;
+            while !visited[x] {
+                visited[x] = true;
+                cycle.push(x);
+                x = vals[x];
+            }
+            if cycle.len() <= step {
+                continue;
+            }
+            for i in (0..cycle.len()).step_by(step) {
+                let from = (i + cycle.len() - step) % cycle.len();
+                marks.set_bit(cycle[i], true)?;
+                back.push((cycle[i], cycle[from]));
+            }
+        }
+        back.sort_unstable();
+        let width = utils::needed_bits(n.saturating_sub(1));
+        let back: Vec<usize> = back.into_iter().map(|(_, from)| from).collect();
+        Ok(Self {
+            perm: CompactVector::from_slice_with_width(vals, width)?,
+            marks: marks.freeze::<Rank9SelIndex>(),
+            back: CompactVector::from_slice_with_width(&back, width)?,
+            step,
+        })
+    }
+
+    /// Returns $`\pi(i)`$, or [`None`] if `self.len() <= i`.
+    ///
+    /// # Complexity
+    ///
+    /// Constant
+    pub fn apply(&self, i: usize) -> Option<usize> {
+        self.perm.get_int(i)
+    }
+
+    /// Returns $`\pi^{-1}(j)`$, or [`None`] if `self.len() <= j`.
+    ///
+    /// # Complexity
+    ///
+    /// $`O(t)`$ for the step $`t`$
+    pub fn inverse(&self, j: usize) -> Option<usize> {
+        if self.len() <= j {
+            return None;
+        }
+        let mut x = j;
+        let mut jumped = false;
+        loop {
+            let next = self.perm.get_int(x).unwrap();
+            if next == j {
+                return Some(x);
+            }
+            if !jumped && self.marks.access(x).unwrap() {
+                x = self.back.get_int(self.marks.rank1(x).unwrap()).unwrap();
+                jumped = true;
+            } else {
+                x = next;
+            }
+        }
+    }
+
+    /// Gets the step between marked elements on a cycle.
+    pub const fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Gets the number of elements.
+    pub const fn len(&self) -> usize {
+        self.perm.len()
+    }
+
+    /// Checks if the permutation is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    #[test]
+    fn test_inverse() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        for n in [0, 1, 2, 100, 1000] {
+            let mut vals: Vec<usize> = (0..n).collect();
+            vals.shuffle(&mut rng);
+            for step in [1, 2, 5, Permutation::DEFAULT_STEP] {
+                let perm = Permutation::from_slice_with_step(&vals, step).unwrap();
+                assert_eq!(perm.len(), n);
+                for (i, &x) in vals.iter().enumerate() {
+                    assert_eq!(perm.apply(i), Some(x));
+                    assert_eq!(perm.inverse(perm.apply(i).unwrap()), Some(i));
+                }
+                assert_eq!(perm.apply(n), None);
+                assert_eq!(perm.inverse(n), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_cycle() {
+        let n = 1000;
+        let vals: Vec<usize> = (0..n).map(|i| (i + 1) % n).collect();
+        let perm = Permutation::from_slice_with_step(&vals, 7).unwrap();
+        for j in 0..n {
+            assert_eq!(perm.inverse(j), Some((j + n - 1) % n));
+        }
+        let identity: Vec<usize> = (0..n).collect();
+        let perm = Permutation::from_slice(&identity).unwrap();
+        for j in 0..n {
+            assert_eq!(perm.inverse(j), Some(j));
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        let e = Permutation::from_slice(&[0, 2, 2]);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some(
+                "vals must be a permutation of 0..3, but got 2 out of range or repeated."
+                    .to_string()
+            )
+        );
+        assert!(Permutation::from_slice(&[1, 2]).is_err());
+        let e = Permutation::from_slice_with_step(&[0], 0);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("step must be at least 1, but got 0.".to_string())
+        );
+    }
+}
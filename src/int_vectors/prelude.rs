@@ -6,4 +6,4 @@
 //! # #![allow(unused_imports)]
 //! use jerky::int_vectors::prelude::*;
 //! ```
-pub use crate::int_vectors::{Access, Build, NumVals};
+pub use crate::int_vectors::{Access, Build, IntSequence, NumVals};
@@ -0,0 +1,157 @@
+//! Static map from sparse integer keys to integer values.
+#![cfg(target_pointer_width = "64")]
+
+use anyhow::{anyhow, Result};
+
+use crate::int_vectors::{Access, CompactVector, MonotoneVector};
+
+/// Static map from integer keys to integer values, i.e., a succinct associative array.
+///
+/// The keys are stored in a [`MonotoneVector`], i.e., in Elias-Fano encoding,
+/// and the values are stored in a [`CompactVector`] in key order,
+/// so the value of a key is found at the rank of the key among the keys.
+/// For $`n`$ keys with the largest key $`u - 1`$, the map takes
+/// $`n (\lfloor \lg (u / n) \rfloor + 3) + o(n) + n \lceil \lg v \rceil`$ bits,
+/// where $`v - 1`$ is the largest value, so sparse keys from a huge key space stay cheap.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::int_vectors::RankIndexedMap;
+///
+/// let map = RankIndexedMap::from_pairs(&[(2, 20), (5, 7), (9, 0)])?;
+///
+/// assert_eq!(map.len(), 3);
+/// assert_eq!(map.get(5), Some(7));
+/// assert_eq!(map.get(9), Some(0));
+/// assert_eq!(map.get(3), None);
+/// assert_eq!(map.get(10), None);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankIndexedMap {
+    keys: MonotoneVector,
+    values: CompactVector,
+}
+
+impl Default for RankIndexedMap {
+    fn default() -> Self {
+        Self::from_pairs(&[]).unwrap()
+    }
+}
+
+impl RankIndexedMap {
+    /// Creates a new map from `(key, value)` pairs sorted by key.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the keys are not strictly increasing.
+    pub fn from_pairs(pairs: &[(usize, usize)]) -> Result<Self> {
+        for w in pairs.windows(2) {
+            let (prev, key) = (w[0].0, w[1].0);
+            if key <= prev {
+                return Err(anyhow!(
+                    "keys must be strictly increasing, but got {key} after {prev}."
+                ));
+            }
+        }
+        let keys: Vec<usize> = pairs.iter().map(|&(key, _)| key).collect();
+        let values: Vec<usize> = pairs.iter().map(|&(_, value)| value).collect();
+        Ok(Self {
+            keys: MonotoneVector::from_slice(&keys)?,
+            values: CompactVector::from_slice(&values)?,
+        })
+    }
+
+    /// Returns the value of `key`, or [`None`] if `key` is absent.
+    ///
+    /// # Complexity
+    ///
+    /// Same as [`MonotoneVector::rank`].
+    pub fn get(&self, key: usize) -> Option<usize> {
+        self.values.get_int(self.position(key)?)
+    }
+
+    /// Checks if `key` is present.
+    pub fn contains_key(&self, key: usize) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Returns the position of `key` among the keys, or [`None`] if `key` is absent.
+    fn position(&self, key: usize) -> Option<usize> {
+        let pos = self.keys.rank(key);
+        if self.keys.access(pos)? == key {
+            Some(pos)
+        } else {
+            None
+        }
+    }
+
+    /// Gets the number of keys.
+    pub const fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Checks if the map is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    #[test]
+    fn test_get() {
+        let mut rng = ChaChaRng::seed_from_u64(334);
+        let mut keys: Vec<usize> = (0..500).map(|_| rng.gen_range(0..10000)).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        let pairs: Vec<(usize, usize)> = keys.iter().map(|&k| (k, k * 3 + 1)).collect();
+        let map = RankIndexedMap::from_pairs(&pairs).unwrap();
+        assert_eq!(map.len(), pairs.len());
+        for key in 0..=10001 {
+            let expected = keys.binary_search(&key).ok().map(|i| pairs[i].1);
+            assert_eq!(map.get(key), expected, "key={key}");
+            assert_eq!(map.contains_key(key), expected.is_some());
+        }
+    }
+
+    #[test]
+    fn test_boundary_keys() {
+        let map = RankIndexedMap::from_pairs(&[(0, 5), (63, 0), (64, usize::MAX)]).unwrap();
+        assert_eq!(map.get(0), Some(5));
+        assert_eq!(map.get(63), Some(0));
+        assert_eq!(map.get(64), Some(usize::MAX));
+        assert_eq!(map.get(1), None);
+        assert_eq!(map.get(65), None);
+        assert_eq!(map.get(usize::MAX), None);
+
+        let map = RankIndexedMap::from_pairs(&[(3, 1), (1 << 62, 2), (usize::MAX, 3)]).unwrap();
+        assert_eq!(map.get(3), Some(1));
+        assert_eq!(map.get(1 << 62), Some(2));
+        assert_eq!(map.get(usize::MAX), Some(3));
+        assert_eq!(map.get(usize::MAX - 1), None);
+        assert_eq!(map.get(0), None);
+        assert!(!map.contains_key(4));
+
+        let map = RankIndexedMap::default();
+        assert!(map.is_empty());
+        assert_eq!(map.get(0), None);
+    }
+
+    #[test]
+    fn test_unsorted() {
+        let e = RankIndexedMap::from_pairs(&[(1, 0), (3, 0), (3, 1)]);
+        assert_eq!(
+            e.err().map(|x| x.to_string()),
+            Some("keys must be strictly increasing, but got 3 after 3.".to_string())
+        );
+        assert!(RankIndexedMap::from_pairs(&[(2, 0), (1, 0)]).is_err());
+    }
+}
@@ -33,13 +33,15 @@
 //! - [Character sequences](crate::char_sequences)
 //!
 //! The descriptions for each category are available in the corresponding module.
+//! The [`prelude`] imports the common structures and traits at once.
 //!
 //! Throughout this document, we write $`\log_2`$ with $`\lg`$.
 //!
 //! ## Serialization
 //!
-//! The previous copying based serialization infrastructure has been removed and
-//! will be replaced with zero‑copy utilities in the future.
+//! Structures are serialized into zero-copy [`anybytes::Bytes`] buffers together
+//! with a small metadata value needed to reconstruct them.
+//! The [`Serializable`] trait unifies these `to_bytes`/`from_bytes` pairs.
 //!
 //! ## Limitation
 //!
@@ -54,12 +56,17 @@ pub mod bit_vector;
 pub mod broadword;
 pub mod char_sequences;
 pub mod data;
+pub mod freeze;
 pub mod int_vectors;
 mod intrinsics;
+pub mod prelude;
+pub mod serialization;
 pub mod utils;
 
 pub use bit_vector::{BitVector, BitVectorData, BitVectorIndex, NoIndex};
 pub use data::IntVectorData;
+pub use freeze::Freeze;
+pub use serialization::Serializable;
 
 // NOTE(kampersanda): We should not use `get()` because it has been already used in most std
 // containers with different type annotations.
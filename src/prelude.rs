@@ -0,0 +1,39 @@
+//! The prelude for commonly used types and traits.
+//!
+//! The purpose of this module is to get started with a single import
+//! instead of spelling out the module path of each structure.
+//! The query traits of bit vectors are exported by name, and those of integer vectors
+//! anonymously since both families have an `Access` trait.
+//!
+//! ```
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use jerky::prelude::*;
+//!
+//! let mut builder = BitVectorBuilder::new();
+//! builder.extend_bits([true, false, false, true])?;
+//! let bv: BitVector<Rank9SelIndex> = builder.freeze();
+//! assert_eq!(bv.num_ones(), 2);
+//! assert_eq!(bv.access(1), Some(false));
+//! assert_eq!(bv.rank1(4), Some(2));
+//! assert_eq!(bv.select1(1), Some(3));
+//!
+//! let plain: BitVector<NoIndex> = bv.with_index();
+//! assert_eq!(plain.select0(1), Some(2));
+//!
+//! let cv = CompactVector::from_slice(&[5, 256, 0])?;
+//! assert_eq!(cv.access(1), Some(256));
+//!
+//! let seq: DacsByte = DacsByte::build_from_slice(&[5, 0, 100000])?;
+//! assert_eq!(seq.num_vals(), 3);
+//! assert_eq!(seq.access(2), Some(100000));
+//! # Ok(())
+//! # }
+//! ```
+pub use crate::bit_vector::{
+    Access, BitVector, BitVectorBuilder, BitVectorData, BitVectorIndex, NoIndex, NumBits, Rank,
+    Rank9SelIndex, Select,
+};
+pub use crate::freeze::Freeze;
+pub use crate::int_vectors::Access as _;
+pub use crate::int_vectors::{Build, CompactVector, DacsByte, IntSequence, NumVals};
+pub use crate::serialization::Serializable;
@@ -0,0 +1,133 @@
+//! Unified interface for zero-copy serialization.
+#![cfg(target_pointer_width = "64")]
+
+use anybytes::Bytes;
+use anyhow::Result;
+
+use crate::bit_vector::{BitVectorData, BitVectorIndex};
+use crate::char_sequences::wavelet_matrix::{WaveletMatrix, WaveletMatrixMeta};
+use crate::int_vectors::compact_vector::{CompactVector, CompactVectorMeta};
+use crate::int_vectors::dacs_byte::{DacsByte, DacsByteMeta};
+
+/// Interface for structures serialized into zero-copy [`Bytes`] plus metadata.
+///
+/// Each implementation forwards to the inherent `to_bytes` and `from_bytes`
+/// methods of the structure, so generic persistence code can handle any of them.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use jerky::int_vectors::CompactVector;
+/// use jerky::Serializable;
+///
+/// fn roundtrip<T: Serializable>(x: &T) -> anyhow::Result<T> {
+///     let (meta, bytes) = x.to_bytes();
+///     T::from_bytes(meta, bytes)
+/// }
+///
+/// let cv = CompactVector::from_slice(&[5, 256, 0])?;
+/// assert_eq!(roundtrip(&cv)?, cv);
+/// # Ok(())
+/// # }
+/// ```
+pub trait Serializable: Sized {
+    /// Metadata required to reconstruct the structure from its bytes.
+    type Meta;
+
+    /// Serializes the structure into a [`Bytes`] buffer along with its metadata.
+    fn to_bytes(&self) -> (Self::Meta, Bytes);
+
+    /// Reconstructs the structure from metadata and a zero-copy [`Bytes`] buffer.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if `bytes` is inconsistent with `meta`.
+    fn from_bytes(meta: Self::Meta, bytes: Bytes) -> Result<Self>;
+}
+
+impl Serializable for BitVectorData {
+    /// Number of bits stored.
+    type Meta = usize;
+
+    fn to_bytes(&self) -> (Self::Meta, Bytes) {
+        BitVectorData::to_bytes(self)
+    }
+
+    fn from_bytes(meta: Self::Meta, bytes: Bytes) -> Result<Self> {
+        BitVectorData::from_bytes(meta, bytes)
+    }
+}
+
+impl Serializable for CompactVector {
+    type Meta = CompactVectorMeta;
+
+    fn to_bytes(&self) -> (Self::Meta, Bytes) {
+        CompactVector::to_bytes(self)
+    }
+
+    fn from_bytes(meta: Self::Meta, bytes: Bytes) -> Result<Self> {
+        CompactVector::from_bytes(meta, bytes)
+    }
+}
+
+impl<I: BitVectorIndex> Serializable for DacsByte<I> {
+    type Meta = DacsByteMeta;
+
+    fn to_bytes(&self) -> (Self::Meta, Bytes) {
+        DacsByte::to_bytes(self)
+    }
+
+    fn from_bytes(meta: Self::Meta, bytes: Bytes) -> Result<Self> {
+        DacsByte::from_bytes(meta, bytes)
+    }
+}
+
+impl<I: BitVectorIndex> Serializable for WaveletMatrix<I> {
+    type Meta = WaveletMatrixMeta;
+
+    fn to_bytes(&self) -> (Self::Meta, Bytes) {
+        WaveletMatrix::to_bytes(self)
+    }
+
+    fn from_bytes(meta: Self::Meta, bytes: Bytes) -> Result<Self> {
+        WaveletMatrix::from_bytes(meta, bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bit_vector::Rank9SelIndex;
+
+    fn roundtrip<T>(x: T)
+    where
+        T: Serializable + PartialEq + core::fmt::Debug,
+    {
+        let (meta, bytes) = x.to_bytes();
+        let other = T::from_bytes(meta, bytes).unwrap();
+        assert_eq!(x, other);
+    }
+
+    #[test]
+    fn bit_vector_data_roundtrip() {
+        roundtrip(BitVectorData::from_bits([true, false, true, true, false]));
+    }
+
+    #[test]
+    fn compact_vector_roundtrip() {
+        roundtrip(CompactVector::from_slice(&[4, 5, 6]).unwrap());
+    }
+
+    #[test]
+    fn dacs_byte_roundtrip() {
+        roundtrip(DacsByte::<Rank9SelIndex>::from_slice(&[5, 0, 100000, 334]).unwrap());
+    }
+
+    #[test]
+    fn wavelet_matrix_roundtrip() {
+        let cv = CompactVector::from_slice(&[3, 1, 4, 1, 5, 9, 2, 6]).unwrap();
+        roundtrip(WaveletMatrix::<Rank9SelIndex>::new(cv).unwrap());
+    }
+}
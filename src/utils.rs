@@ -5,6 +5,9 @@ use crate::broadword;
 
 /// Returns the number of bits to represent `x` at least.
 ///
+/// `needed_bits(0)` is 1, not 0, so that the result is always a valid width
+/// of [`CompactVector`](crate::int_vectors::CompactVector), e.g., for a vector of zeros.
+///
 /// # Examples
 ///
 /// ```
@@ -20,7 +23,7 @@ pub fn needed_bits(x: usize) -> usize {
     broadword::msb(x).map_or(1, |n| n + 1)
 }
 
-/// Returns `ceil(x / y)`.
+/// Returns `ceil(x / y)` without overflowing for any `x`.
 ///
 /// # Examples
 ///
@@ -29,11 +32,36 @@ pub fn needed_bits(x: usize) -> usize {
 ///
 /// assert_eq!(ceiled_divide(10, 2), 5);
 /// assert_eq!(ceiled_divide(10, 3), 4);
+/// assert_eq!(ceiled_divide(usize::MAX, 64), usize::MAX / 64 + 1);
 /// ```
 ///
 /// # Panics
 ///
 /// It will panic if `y == 0`.
 pub const fn ceiled_divide(x: usize, y: usize) -> usize {
-    (x + y - 1) / y
+    x / y + (x % y != 0) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_needed_bits() {
+        assert_eq!(needed_bits(0), 1);
+        assert_eq!(needed_bits(1), 1);
+        assert_eq!(needed_bits(usize::MAX), 64);
+        assert_eq!(needed_bits(usize::MAX >> 1), 63);
+        assert_eq!(needed_bits(1 << 63), 64);
+    }
+
+    #[test]
+    fn test_ceiled_divide() {
+        assert_eq!(ceiled_divide(0, 7), 0);
+        assert_eq!(ceiled_divide(7, 7), 1);
+        assert_eq!(ceiled_divide(8, 7), 2);
+        assert_eq!(ceiled_divide(usize::MAX, 1), usize::MAX);
+        assert_eq!(ceiled_divide(usize::MAX, 2), usize::MAX / 2 + 1);
+        assert_eq!(ceiled_divide(usize::MAX - 63, 64), usize::MAX / 64);
+    }
 }